@@ -0,0 +1,616 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ * It talks to the Advent of Code website on behalf of the `download` binary.
+ */
+pub mod markdown;
+pub mod submit;
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, process};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SESSION_FILE: &str = ".adventofcode.session";
+
+const USER_AGENT: &str = "github.com/DevonMorris/aoc-rust-2022 via ureq";
+
+#[derive(Debug)]
+pub enum AocError {
+    MissingSession,
+    Status { code: u16, body: String },
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {} or create \"~/{}\".",
+                SESSION_ENV, SESSION_FILE
+            ),
+            AocError::Status { code, body } => {
+                write!(f, "server responded with status {}", code)?;
+                match body.lines().next() {
+                    Some(line) if !line.trim().is_empty() => write!(f, ": {}", line.trim()),
+                    _ => Ok(()),
+                }
+            }
+            AocError::Transport(e) => write!(f, "request failed: {}", e),
+            AocError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+/// Reads the session cookie from `AOC_SESSION`, falling back to `~/.adventofcode.session`.
+pub fn read_session() -> Result<String, AocError> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    let path = session_file_path().ok_or(AocError::MissingSession)?;
+    match fs::read_to_string(path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        Ok(_) => Err(AocError::MissingSession),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(AocError::MissingSession),
+        Err(e) => Err(AocError::Io(e)),
+    }
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(SESSION_FILE))
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Builds a client from the environment. An explicit `base_url` wins over `AOC_BASE_URL`.
+    pub fn from_env(base_url: Option<String>) -> Result<Self, AocError> {
+        let base_url = base_url
+            .or_else(|| env::var(BASE_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Self::new(&base_url, &read_session()?))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn input(&self, year: i32, day: u8) -> Result<String, AocError> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    pub fn puzzle(&self, year: i32, day: u8) -> Result<String, AocError> {
+        self.get(&format!("/{}/day/{}", year, day))
+    }
+
+    /// Posts an answer and returns the raw response page. See `submit::parse_verdict`.
+    pub fn submit(&self, year: i32, day: u8, part: u8, answer: &str) -> Result<String, AocError> {
+        let request = self
+            .agent
+            .post(&format!("{}/{}/day/{}/answer", self.base_url, year, day))
+            .set("Cookie", &format!("session={}", self.session));
+        read_response(request.send_form(&[("level", &part.to_string()), ("answer", answer)]))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocError> {
+        let request = self
+            .agent
+            .get(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session));
+        read_response(request.call())
+    }
+}
+
+fn read_response(result: Result<ureq::Response, ureq::Error>) -> Result<String, AocError> {
+    match result {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(code, response)) => Err(AocError::Status {
+            code,
+            body: response.into_string().unwrap_or_default(),
+        }),
+        Err(ureq::Error::Transport(e)) => Err(AocError::Transport(e.to_string())),
+    }
+}
+
+/// Writes `contents` to a sibling temp file and renames it over `path`,
+/// so readers never observe a partially written file and concurrent writers do not clash.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?
+        .to_string_lossy();
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name,
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidInput {
+    Empty,
+    Html,
+}
+
+impl fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "input is empty"),
+            InvalidInput::Html => write!(
+                f,
+                "input contains an HTML page (is your session cookie expired?)"
+            ),
+        }
+    }
+}
+
+/// Rejects inputs that are blank or that are actually an HTML page served in place of the input.
+pub fn validate_input(contents: &str) -> Result<(), InvalidInput> {
+    if contents.trim().is_empty() {
+        return Err(InvalidInput::Empty);
+    }
+    let lower = contents.to_ascii_lowercase();
+    if lower.contains("<!doctype html") || lower.contains("<html") {
+        return Err(InvalidInput::Html);
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CachedInput {
+    Missing,
+    Valid,
+    Invalid(InvalidInput),
+}
+
+/// Where the input for `day` of `year` is cached. The solutions read `src/inputs/NN.txt`,
+/// which holds a copy of the cached input of whichever year was downloaded last.
+pub fn input_cache_path(year: i32, day: u8) -> PathBuf {
+    PathBuf::from(format!("src/inputs/{}/{:02}.txt", year, day))
+}
+
+pub fn check_cached_input(path: &Path) -> io::Result<CachedInput> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(match validate_input(&contents) {
+            Ok(_) => CachedInput::Valid,
+            Err(e) => CachedInput::Invalid(e),
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(CachedInput::Missing),
+        Err(e) => Err(e),
+    }
+}
+
+/// Copies a non-empty `path` to `path.bak`, returning the backup location if one was made.
+pub fn backup_file(path: &Path) -> io::Result<Option<PathBuf>> {
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() > 0 => {
+            let mut backup = path.as_os_str().to_owned();
+            backup.push(".bak");
+            let backup = PathBuf::from(backup);
+            fs::copy(path, &backup)?;
+            Ok(Some(backup))
+        }
+        Ok(_) => Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Converts days since 1970-01-01 into a (year, month, day) civil date.
+/// see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+    (year, month, day)
+}
+
+/// Converts a (year, month, day) civil date into days since 1970-01-01.
+/// see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = i64::from(year) - i64::from(month <= 2);
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Puzzles unlock at midnight US Eastern time, which is always EST (UTC-5) in December.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+pub const FIRST_EVENT_YEAR: i32 = 2015;
+
+/// Events ran for 25 days until 2024 and for 12 days since 2025.
+pub fn days_in_event(year: i32) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Unavailable {
+    InvalidDay {
+        day: u8,
+        year: i32,
+    },
+    InvalidYear(i32),
+    Locked {
+        unlocks_at: SystemTime,
+        remaining: Duration,
+    },
+}
+
+impl fmt::Display for Unavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unavailable::InvalidDay { day, year } => write!(
+                f,
+                "day {} does not exist, days in {} range from 1 to {}.",
+                day,
+                year,
+                days_in_event(*year)
+            ),
+            Unavailable::InvalidYear(year) => write!(
+                f,
+                "year {} has no event, events started in {}.",
+                year, FIRST_EVENT_YEAR
+            ),
+            Unavailable::Locked {
+                unlocks_at,
+                remaining,
+            } => write!(
+                f,
+                "puzzle unlocks at {} (in {}).",
+                format_utc(*unlocks_at),
+                format_duration(*remaining)
+            ),
+        }
+    }
+}
+
+fn unix_secs(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
+}
+
+/// The moment the puzzle for `day` of `year` becomes available.
+pub fn unlock_time(year: i32, day: u8) -> SystemTime {
+    let days = days_from_civil(year, 12, u32::from(day));
+    let secs = days * 86_400 + (UNLOCK_HOUR_UTC * 3600) as i64;
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+/// Checks whether a puzzle can be downloaded at `now`.
+pub fn check_available(year: i32, day: u8, now: SystemTime) -> Result<(), Unavailable> {
+    if year < FIRST_EVENT_YEAR {
+        return Err(Unavailable::InvalidYear(year));
+    }
+    if !(1..=days_in_event(year)).contains(&day) {
+        return Err(Unavailable::InvalidDay { day, year });
+    }
+    let unlocks_at = unlock_time(year, day);
+    match unlocks_at.duration_since(now) {
+        Ok(remaining) if !remaining.is_zero() => Err(Unavailable::Locked {
+            unlocks_at,
+            remaining,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Formats a duration as `[Nd ]HH:MM:SS`, rounding up to the next second.
+pub fn format_duration(d: Duration) -> String {
+    let mut secs = d.as_secs();
+    if d.subsec_nanos() > 0 {
+        secs += 1;
+    }
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{}d {}", days, clock)
+    } else {
+        clock
+    }
+}
+
+/// Formats a point in time as `YYYY-MM-DD HH:MM UTC`.
+pub fn format_utc(time: SystemTime) -> String {
+    let secs = unix_secs(time);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let rest = secs.rem_euclid(86_400);
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest % 3600 / 60
+    )
+}
+
+/// The most recent event year at `now`: the current year during December, the previous one otherwise.
+pub fn latest_event_year(now: SystemTime) -> i32 {
+    let (year, month, _) = civil_from_days(unix_secs(now).div_euclid(86_400));
+    if month == 12 {
+        year
+    } else {
+        year - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves a single canned HTTP response and hands back the raw request it received.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let mut req_body = vec![0; content_length];
+            reader.read_exact(&mut req_body).unwrap();
+            request.push_str(&String::from_utf8(req_body).unwrap());
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_input_download() {
+        let (base_url, server) = serve_once("200 OK", "1000\n2000\n");
+        let client = AocClient::new(&base_url, "abc123");
+        assert_eq!(client.input(2022, 1).unwrap(), "1000\n2000\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=abc123"));
+    }
+
+    #[test]
+    fn test_input_download_error_status() {
+        let (base_url, server) = serve_once("400 Bad Request", "Puzzle inputs differ by user.");
+        let client = AocClient::new(&format!("{}/", base_url), "expired");
+        match client.input(2022, 1) {
+            Err(AocError::Status { code, body }) => {
+                assert_eq!(code, 400);
+                assert_eq!(body, "Puzzle inputs differ by user.");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_answer_submission() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<html><main><article><p>That's the right answer!</p></article></main></html>",
+        );
+        let client = AocClient::new(&base_url, "abc123");
+        let page = client.submit(2022, 5, 2, "MCD").unwrap();
+        assert_eq!(submit::parse_verdict(&page), submit::Verdict::Correct);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/5/answer HTTP/1.1"));
+        assert!(request.contains("session=abc123"));
+        assert!(request.ends_with("level=2&answer=MCD"));
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = env::temp_dir().join(format!("aoc_write_atomic_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt");
+
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_input() {
+        assert_eq!(validate_input("1000\n2000\n"), Ok(()));
+        assert_eq!(validate_input(""), Err(InvalidInput::Empty));
+        assert_eq!(validate_input(" \n\n"), Err(InvalidInput::Empty));
+        assert_eq!(
+            validate_input("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InvalidInput::Html)
+        );
+    }
+
+    #[test]
+    fn test_cached_input_and_backup() {
+        let dir = env::temp_dir().join(format!("aoc_cached_input_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt");
+
+        assert_eq!(check_cached_input(&path).unwrap(), CachedInput::Missing);
+        assert_eq!(backup_file(&path).unwrap(), None);
+
+        fs::write(&path, "").unwrap();
+        assert_eq!(
+            check_cached_input(&path).unwrap(),
+            CachedInput::Invalid(InvalidInput::Empty)
+        );
+        assert_eq!(backup_file(&path).unwrap(), None);
+
+        fs::write(&path, "1000\n").unwrap();
+        assert_eq!(check_cached_input(&path).unwrap(), CachedInput::Valid);
+        let backup = backup_file(&path).unwrap().unwrap();
+        assert_eq!(backup, dir.join("01.txt.bak"));
+        assert_eq!(fs::read_to_string(backup).unwrap(), "1000\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_input_cache_path() {
+        assert_eq!(
+            input_cache_path(2022, 7),
+            PathBuf::from("src/inputs/2022/07.txt")
+        );
+        assert_ne!(input_cache_path(2021, 7), input_cache_path(2022, 7));
+    }
+
+    #[test]
+    fn test_latest_event_year() {
+        let at = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+        // 2022-11-30T23:59:59Z
+        assert_eq!(latest_event_year(at(1_669_852_799)), 2021);
+        // 2022-12-01T00:00:00Z
+        assert_eq!(latest_event_year(at(1_669_852_800)), 2022);
+        // 2023-01-15T12:00:00Z
+        assert_eq!(latest_event_year(at(1_673_784_000)), 2022);
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        let at = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+        // 2022-12-01T05:00:00Z, midnight EST.
+        assert_eq!(unlock_time(2022, 1), at(1_669_870_800));
+        assert_eq!(unlock_time(2022, 25), at(1_669_870_800 + 24 * 86_400));
+        assert_eq!(format_utc(unlock_time(2015, 7)), "2015-12-07 05:00 UTC");
+        for days in [-1, 0, 59, 10_957, 19_327, 100_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn test_check_available() {
+        let unlock = unlock_time(2022, 8);
+        assert_eq!(check_available(2022, 8, unlock), Ok(()));
+        assert_eq!(
+            check_available(2022, 8, unlock - Duration::from_secs(90)),
+            Err(Unavailable::Locked {
+                unlocks_at: unlock,
+                remaining: Duration::from_secs(90)
+            })
+        );
+        assert_eq!(
+            check_available(2022, 26, unlock),
+            Err(Unavailable::InvalidDay {
+                day: 26,
+                year: 2022
+            })
+        );
+        let later = unlock_time(2025, 12);
+        assert_eq!(check_available(2025, 12, later), Ok(()));
+        assert_eq!(
+            check_available(2025, 13, later).unwrap_err().to_string(),
+            "day 13 does not exist, days in 2025 range from 1 to 12."
+        );
+        assert_eq!(
+            check_available(2014, 1, unlock),
+            Err(Unavailable::InvalidYear(2014))
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(0)), "00:00:00");
+        assert_eq!(format_duration(Duration::from_millis(59_001)), "00:01:00");
+        assert_eq!(
+            format_duration(Duration::from_secs(3 * 86_400 + 3723)),
+            "3d 01:02:03"
+        );
+    }
+}
@@ -0,0 +1,378 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ * It turns puzzle pages into Markdown without pulling in a full HTML parser.
+ */
+
+enum Token<'a> {
+    Text(&'a str),
+    Start { name: String, attrs: &'a str },
+    End { name: String },
+}
+
+/// Splits HTML into text and tags. Comments, doctypes and anything malformed are skipped.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if open > 0 {
+            tokens.push(Token::Text(&rest[..open]));
+        }
+        rest = &rest[open..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let tag = rest[1..close].trim_end_matches('/');
+        rest = &rest[close + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::End {
+                name: name.trim().to_ascii_lowercase(),
+            });
+        } else if !tag.starts_with('!') {
+            let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(Token::Start {
+                name: name.to_ascii_lowercase(),
+                attrs,
+            });
+        }
+    }
+    tokens
+}
+
+fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{}=\"", name))? + name.len() + 2;
+    let len = attrs[start..].find('"')?;
+    Some(&attrs[start..start + len])
+}
+
+/// Decodes the named entities that show up on puzzle pages plus numeric references.
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let c = match &rest[1..semi] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let code = match entity.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+struct Renderer<'a> {
+    base_url: &'a str,
+    out: String,
+    in_pre: bool,
+    in_code: bool,
+    lists: usize,
+    links: Vec<String>,
+}
+
+impl<'a> Renderer<'a> {
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+        if self.in_pre {
+            self.out.push_str(&text);
+            return;
+        }
+        for (i, word) in text.split_whitespace().enumerate() {
+            let starts_with_space = text.starts_with(char::is_whitespace);
+            if (i > 0 || starts_with_space) && !self.out.ends_with([' ', '\n', '[', '(']) {
+                self.out.push(' ');
+            }
+            self.out.push_str(word);
+        }
+        if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+            self.out.push(' ');
+        }
+    }
+
+    fn block_break(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push_str(if self.out.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+    }
+
+    fn start(&mut self, name: &str, attrs: &str) {
+        match name {
+            "h2" => {
+                self.block_break();
+                self.out.push_str("## ");
+            }
+            "p" => self.block_break(),
+            "pre" => {
+                self.block_break();
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            "code" if !self.in_pre => {
+                self.out.push('`');
+                self.in_code = true;
+            }
+            "em" if !self.in_pre && !self.in_code => self.out.push('*'),
+            "ul" => {
+                if self.lists == 0 {
+                    self.block_break();
+                }
+                self.lists += 1;
+            }
+            "li" => {
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out
+                    .push_str(&"  ".repeat(self.lists.saturating_sub(1)));
+                self.out.push_str("- ");
+            }
+            "a" => {
+                let href = attr(attrs, "href").unwrap_or_default();
+                let href = if href.starts_with('/') {
+                    format!("{}{}", self.base_url, href)
+                } else {
+                    href.to_string()
+                };
+                self.links.push(href);
+                self.out.push('[');
+            }
+            "br" => self.out.push('\n'),
+            _ => (),
+        }
+    }
+
+    fn end(&mut self, name: &str) {
+        match name {
+            "h2" | "p" => self.block_break(),
+            "pre" => {
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```");
+                self.in_pre = false;
+                self.block_break();
+            }
+            "code" if !self.in_pre => {
+                self.out.push('`');
+                self.in_code = false;
+            }
+            "em" if !self.in_pre && !self.in_code => self.out.push('*'),
+            "ul" => {
+                self.lists = self.lists.saturating_sub(1);
+                if self.lists == 0 {
+                    self.block_break();
+                }
+            }
+            "a" => {
+                let href = self.links.pop().unwrap_or_default();
+                self.out.push_str(&format!("]({})", href));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Renders every `<article class="day-desc">` on a puzzle page as Markdown.
+/// Relative links are resolved against `base_url`.
+pub fn puzzle_to_markdown(html: &str, base_url: &str) -> Option<String> {
+    let mut renderer = Renderer {
+        base_url: base_url.trim_end_matches('/'),
+        out: String::new(),
+        in_pre: false,
+        in_code: false,
+        lists: 0,
+        links: Vec::new(),
+    };
+
+    let mut in_article = false;
+    let mut found = false;
+    for token in tokenize(html) {
+        match token {
+            Token::Start { name, attrs } if name == "article" => {
+                in_article = attr(attrs, "class") == Some("day-desc");
+                if in_article {
+                    found = true;
+                    renderer.block_break();
+                }
+            }
+            Token::End { name } if name == "article" => in_article = false,
+            _ if !in_article => (),
+            Token::Text(text) => renderer.text(text),
+            Token::Start { name, attrs } => renderer.start(&name, attrs),
+            Token::End { name } => renderer.end(&name),
+        }
+    }
+
+    if !found {
+        return None;
+    }
+    let mut markdown = renderer.out.trim_end().to_string();
+    markdown.push('\n');
+    Some(markdown)
+}
+
+/// Extracts the text of the first `<pre><code>` block, which usually holds the example input.
+pub fn first_code_block(html: &str) -> Option<String> {
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut block = String::new();
+    for token in tokenize(html) {
+        match token {
+            Token::Start { name, .. } if name == "pre" => in_pre = true,
+            Token::Start { name, .. } if name == "code" && in_pre => in_code = true,
+            Token::End { name } if name == "code" && in_code => return Some(block),
+            Token::End { name } if name == "pre" => in_pre = false,
+            Token::Text(text) if in_code => block.push_str(&decode_entities(text)),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Returns the whitespace-normalized text of the first `<article>`, as used by answer responses.
+pub fn article_text(html: &str) -> Option<String> {
+    let mut in_article = false;
+    let mut text = String::new();
+    for token in tokenize(html) {
+        match token {
+            Token::Start { name, .. } if name == "article" => in_article = true,
+            Token::End { name } if name == "article" && in_article => {
+                return Some(text.split_whitespace().collect::<Vec<_>>().join(" "));
+            }
+            Token::Text(t) if in_article => text.push_str(&decode_entities(t)),
+            _ => (),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 1 - Advent of Code 2022</title></head>
+<body><main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>The jungle must be too overgrown for <a href="/2022/about">vehicles</a>.</p>
+<p>For example:</p>
+<pre><code>1000
+2000
+
+&lt;4000&gt;
+</code></pre>
+<ul>
+<li>The first Elf is carrying <code><em>6000</em></code> Calories.</li>
+<li>The <em>second</em> Elf is carrying one item.</li>
+</ul>
+<p>Find the Elf carrying the most Calories.</p>
+</article>
+<p>Your puzzle answer was <code>24000</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Find the <span title="hover text">top three</span> Elves.</p>
+<pre><code>ignored</code></pre>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_puzzle_to_markdown() {
+        let markdown = puzzle_to_markdown(PAGE, "https://adventofcode.com/").unwrap();
+        assert_eq!(
+            markdown,
+            "## --- Day 1: Calorie Counting ---
+
+The jungle must be too overgrown for [vehicles](https://adventofcode.com/2022/about).
+
+For example:
+
+```
+1000
+2000
+
+<4000>
+```
+
+- The first Elf is carrying `6000` Calories.
+- The *second* Elf is carrying one item.
+
+Find the Elf carrying the most Calories.
+
+## --- Part Two ---
+
+Find the top three Elves.
+
+```
+ignored
+```
+"
+        );
+        assert_eq!(puzzle_to_markdown("<html></html>", ""), None);
+    }
+
+    #[test]
+    fn test_first_code_block() {
+        assert_eq!(
+            first_code_block(PAGE),
+            Some("1000\n2000\n\n<4000>\n".to_string())
+        );
+        assert_eq!(first_code_block("<p><code>inline</code></p>"), None);
+    }
+
+    #[test]
+    fn test_article_text() {
+        assert_eq!(
+            article_text(
+                "<main><article><p>That's the <em>right</em>\n answer!</p></article></main>"
+            ),
+            Some("That's the right answer!".to_string())
+        );
+        assert_eq!(article_text("<p>no article</p>"), None);
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &amp;&lt;b&gt; &#39;c&#x27; & d"),
+            "a &<b> 'c' & d"
+        );
+    }
+}
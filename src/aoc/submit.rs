@@ -0,0 +1,415 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ * It interprets answer responses and remembers past guesses for the `submit` binary.
+ */
+use super::markdown;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh { wait: Option<u64> },
+    TooLow { wait: Option<u64> },
+    Wrong { wait: Option<u64> },
+    Wait { seconds: Option<u64> },
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh { .. } => write!(f, "That's not the right answer; it is too high."),
+            Verdict::TooLow { .. } => write!(f, "That's not the right answer; it is too low."),
+            Verdict::Wrong { .. } => write!(f, "That's not the right answer."),
+            Verdict::Wait { seconds: Some(s) } => {
+                write!(f, "You submitted too recently, wait another {}s.", s)
+            }
+            Verdict::Wait { seconds: None } => write!(f, "You submitted too recently."),
+            Verdict::AlreadySolved => write!(f, "This part is already solved or not unlocked."),
+            Verdict::Unknown(text) => write!(f, "Unrecognized response: {}", text),
+        }
+    }
+}
+
+/// Parses durations like "1m 30s", "42s", "one minute" or "5 minutes" out of a response.
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some(idx) = text.find(" left to wait") {
+        let before = &text[..idx];
+        let start = before.rfind("You have ").map_or(0, |i| i + 9);
+        let mut seconds = 0;
+        for part in before[start..].split_whitespace() {
+            let (num, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let num: u64 = num.parse().ok()?;
+            seconds += match unit {
+                "h" => num * 3600,
+                "m" => num * 60,
+                "s" => num,
+                _ => return None,
+            };
+        }
+        return Some(seconds);
+    }
+
+    let idx = text.find("wait ")?;
+    let mut words = text[idx + 5..].split_whitespace();
+    let num = match words.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "minute" | "minutes" => Some(num * 60),
+        "second" | "seconds" => Some(num),
+        _ => None,
+    }
+}
+
+pub fn parse_verdict(html: &str) -> Verdict {
+    let text = markdown::article_text(html).unwrap_or_else(|| html.trim().to_string());
+    let wait = parse_wait(&text);
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("answer is too high") {
+            Verdict::TooHigh { wait }
+        } else if text.contains("answer is too low") {
+            Verdict::TooLow { wait }
+        } else {
+            Verdict::Wrong { wait }
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait { seconds: wait }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Outcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(Outcome::Correct),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            "wrong" => Some(Outcome::Wrong),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub part: u8,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    AlreadyGuessed { outcome: Outcome },
+    OutOfBounds { bound: String, outcome: Outcome },
+    RateLimited { seconds: u64 },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with \"{}\".", answer)
+            }
+            Refusal::AlreadyGuessed { outcome } => write!(
+                f,
+                "this answer was already submitted and was {}.",
+                outcome.as_str()
+            ),
+            Refusal::OutOfBounds { bound, outcome } => write!(
+                f,
+                "\"{}\" was already {}, so this answer is too.",
+                bound,
+                outcome.as_str()
+            ),
+            Refusal::RateLimited { seconds } => {
+                write!(f, "submissions are locked for another {}s.", seconds)
+            }
+        }
+    }
+}
+
+/// Past submissions for one day, stored as plain text next to the inputs:
+/// one `<part> <outcome> <answer>` line per guess and an optional `wait-until <unix time>` line.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GuessLog {
+    pub guesses: Vec<Guess>,
+    pub wait_until: Option<u64>,
+}
+
+impl GuessLog {
+    /// Where the guesses for `day` of `year` are kept, so events do not share a log.
+    pub fn path(year: i32, day: u8) -> PathBuf {
+        PathBuf::from(format!("src/answers/{}/{:02}.txt", year, day))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let mut log = GuessLog::default();
+        for line in contents.lines() {
+            let mut fields = line.splitn(3, ' ');
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some("wait-until"), Some(time), None) => log.wait_until = time.parse().ok(),
+                (Some(part), Some(outcome), Some(answer)) => {
+                    if let (Ok(part), Some(outcome)) = (part.parse(), Outcome::from_str(outcome)) {
+                        log.guesses.push(Guess {
+                            part,
+                            outcome,
+                            answer: answer.to_string(),
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+        log
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        super::write_atomic(path, &self.to_string())
+    }
+
+    /// Checks a candidate answer against what is already known, without touching the network.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let guesses = self.guesses.iter().filter(|g| g.part == part);
+        for guess in guesses.clone() {
+            if guess.outcome == Outcome::Correct {
+                return Err(Refusal::AlreadySolved {
+                    answer: guess.answer.clone(),
+                });
+            }
+            if guess.answer == answer {
+                return Err(Refusal::AlreadyGuessed {
+                    outcome: guess.outcome.clone(),
+                });
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            for guess in guesses {
+                let Ok(bound) = guess.answer.parse::<i128>() else {
+                    continue;
+                };
+                let out_of_bounds = match guess.outcome {
+                    Outcome::TooHigh => value >= bound,
+                    Outcome::TooLow => value <= bound,
+                    _ => false,
+                };
+                if out_of_bounds {
+                    return Err(Refusal::OutOfBounds {
+                        bound: guess.answer.clone(),
+                        outcome: guess.outcome.clone(),
+                    });
+                }
+            }
+        }
+
+        match self.wait_until {
+            Some(until) if until > now => Err(Refusal::RateLimited {
+                seconds: until - now,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the server's verdict. Returns `false` if the verdict carried no information to store.
+    pub fn record(&mut self, part: u8, answer: &str, verdict: &Verdict, now: u64) -> bool {
+        let (outcome, wait) = match verdict {
+            Verdict::Correct => (Some(Outcome::Correct), None),
+            Verdict::TooHigh { wait } => (Some(Outcome::TooHigh), *wait),
+            Verdict::TooLow { wait } => (Some(Outcome::TooLow), *wait),
+            Verdict::Wrong { wait } => (Some(Outcome::Wrong), *wait),
+            Verdict::Wait { seconds } => (None, *seconds),
+            Verdict::AlreadySolved | Verdict::Unknown(_) => (None, None),
+        };
+        if let Some(outcome) = &outcome {
+            self.guesses.push(Guess {
+                part,
+                outcome: outcome.clone(),
+                answer: answer.to_string(),
+            });
+        }
+        if let Some(wait) = wait {
+            self.wait_until = Some(now + wait);
+        }
+        outcome.is_some() || wait.is_some()
+    }
+}
+
+impl fmt::Display for GuessLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for guess in &self.guesses {
+            writeln!(
+                f,
+                "{} {} {}",
+                guess.part,
+                guess.outcome.as_str(),
+                guess.answer
+            )?;
+        }
+        if let Some(until) = self.wait_until {
+            writeln!(f, "wait-until {}", until)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(text: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict(&response(
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&response(
+                "That's not the right answer; your answer is too high.  Please wait one minute before trying again. [<a href=\"/2022/day/1\">Return to Day 1</a>]"
+            )),
+            Verdict::TooHigh { wait: Some(60) }
+        );
+        assert_eq!(
+            parse_verdict(&response(
+                "That's not the right answer; your answer is too low.  please wait 5 minutes before trying again."
+            )),
+            Verdict::TooLow { wait: Some(300) }
+        );
+        assert_eq!(
+            parse_verdict(&response("That's not the right answer.")),
+            Verdict::Wrong { wait: None }
+        );
+        assert_eq!(
+            parse_verdict(&response(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 7s left to wait."
+            )),
+            Verdict::Wait { seconds: Some(67) }
+        );
+        assert_eq!(
+            parse_verdict(&response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(parse_verdict("Nope"), Verdict::Unknown("Nope".to_string()));
+    }
+
+    #[test]
+    fn test_guess_log_check() {
+        let log = GuessLog::parse("1 too-high 500\n1 too-low 100\n1 wrong 250\nwait-until 1000\n");
+        assert_eq!(
+            log.check(1, "250", 2000),
+            Err(Refusal::AlreadyGuessed {
+                outcome: Outcome::Wrong
+            })
+        );
+        assert_eq!(
+            log.check(1, "600", 2000),
+            Err(Refusal::OutOfBounds {
+                bound: "500".to_string(),
+                outcome: Outcome::TooHigh
+            })
+        );
+        assert_eq!(
+            log.check(1, "50", 2000),
+            Err(Refusal::OutOfBounds {
+                bound: "100".to_string(),
+                outcome: Outcome::TooLow
+            })
+        );
+        assert_eq!(
+            log.check(1, "300", 400),
+            Err(Refusal::RateLimited { seconds: 600 })
+        );
+        assert_eq!(log.check(1, "300", 2000), Ok(()));
+        assert_eq!(log.check(2, "600", 2000), Ok(()));
+
+        let solved = GuessLog::parse("1 correct 24000\n");
+        assert_eq!(
+            solved.check(1, "24000", 0),
+            Err(Refusal::AlreadySolved {
+                answer: "24000".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_guess_log_path() {
+        assert_eq!(
+            GuessLog::path(2022, 5),
+            PathBuf::from("src/answers/2022/05.txt")
+        );
+        assert_ne!(GuessLog::path(2021, 5), GuessLog::path(2022, 5));
+    }
+
+    #[test]
+    fn test_guess_log_record_roundtrip() {
+        let mut log = GuessLog::default();
+        assert!(log.record(1, "500", &Verdict::TooHigh { wait: Some(60) }, 100));
+        assert!(log.record(1, "CMZ abc", &Verdict::Wrong { wait: None }, 100));
+        assert!(log.record(1, "300", &Verdict::Wait { seconds: Some(30) }, 200));
+        assert!(!log.record(1, "300", &Verdict::AlreadySolved, 200));
+
+        assert_eq!(log.guesses.len(), 2);
+        assert_eq!(log.wait_until, Some(230));
+        assert_eq!(
+            log.to_string(),
+            "1 too-high 500\n1 wrong CMZ abc\nwait-until 230\n"
+        );
+        assert_eq!(GuessLog::parse(&log.to_string()), log);
+    }
+}
@@ -1,5 +1,8 @@
+use advent_of_code::helpers::parse::blocks;
+use advent_of_code::helpers::select::k_largest;
+
 pub fn calculate_calories_per_elf(input: &str) -> Option<Vec<u32>> {
-    let chunks : Vec<&str> = input.split("\n\n").collect();
+    let chunks : Vec<&str> = blocks(input).collect();
     let calories_per_elf_per_item : Vec<Vec<u32>> = chunks.iter().map(|s| -> Vec<u32> { s.lines().map(|l| l.parse().unwrap()).collect() }).collect();
     Some(calories_per_elf_per_item.iter().map(|v| v.iter().sum()).collect())
 }
@@ -9,10 +12,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut calories_per_elf : Vec<u32> = calculate_calories_per_elf(input).unwrap();
-    calories_per_elf.sort();
-    calories_per_elf.reverse();
-    Some(calories_per_elf[0..3].iter().sum())
+    let calories_per_elf : Vec<u32> = calculate_calories_per_elf(input).unwrap();
+    Some(k_largest(calories_per_elf, 3).iter().sum())
 }
 
 fn main() {
@@ -1,50 +1,39 @@
-use std::collections::HashSet;
+use advent_of_code::helpers::bitset::ByteSet;
 
 pub fn score_byte(b: u8) -> u32 {
     if b < 91 {
-        return (b - b'A' + 27).into();
+        (b - b'A' + 27).into()
     } else {
-        return (b - b'a' + 1).into();
+        (b - b'a' + 1).into()
     }
 }
 
+fn score_set(set: ByteSet) -> u32 {
+    set.iter().map(score_byte).sum()
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut compartments1: Vec<HashSet<u8>> = Vec::new();
-    let mut compartments2: Vec<HashSet<u8>> = Vec::new();
-    input.lines().enumerate().for_each(|(i, l)| {
-        compartments1.push(HashSet::new());
-        compartments2.push(HashSet::new());
-        let bytes: Vec<u8> = l.bytes().collect();
-        bytes[..bytes.len() / 2].iter().for_each(|b| {
-            compartments1[i].insert(*b);
-        });
-        bytes[bytes.len() / 2..].iter().for_each(|b| {
-            compartments2[i].insert(*b);
-        });
-    });
-    let mut score: u32 = 0;
-    compartments1
-        .iter()
-        .zip(compartments2.iter())
-        .for_each(|(s1, s2)| s1.intersection(s2).for_each(|v| score += score_byte(*v)));
+    let score = input
+        .lines()
+        .map(|l| {
+            let (first, second) = l.as_bytes().split_at(l.len() / 2);
+            let first: ByteSet = first.iter().copied().collect();
+            let second: ByteSet = second.iter().copied().collect();
+            score_set(first.intersection(second))
+        })
+        .sum();
     Some(score)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let lines : Vec<&str> = input.lines().collect();
+    let lines: Vec<&str> = input.lines().collect();
     let mut score: u32 = 0;
-    for lines in lines.chunks(3) {
-        let mut bags: Vec<HashSet<u8>> = Vec::new();
-        for line in lines {
-            let bag : HashSet<u8> = line.bytes().collect();
-            bags.push(bag);
-        }
-        let mut iter = bags.iter();
-        let base = iter.next().unwrap().clone();
-        let intersection = iter.fold(base, |s1, s2| s1.intersection(&s2).copied().collect());
-        for v in intersection {
-            score += score_byte(v);
-        }
+    for group in lines.chunks(3) {
+        let common = group
+            .iter()
+            .map(|line| line.bytes().collect::<ByteSet>())
+            .reduce(ByteSet::intersection)?;
+        score += score_set(common);
     }
     Some(score)
 }
@@ -1,83 +1,33 @@
-use std::cmp;
-use std::str::FromStr;
-use std::{cmp::Ordering, num::ParseIntError};
+use advent_of_code::helpers::interval::Interval;
 
-#[derive(PartialEq, Copy, Clone)]
-struct Interval {
-    lower: u32,
-    upper: u32,
+fn parse_interval(s: &str) -> Option<Interval<u32>> {
+    let (lower, upper) = s.split_once('-')?;
+    Interval::new(lower.parse().ok()?, upper.parse().ok()?)
 }
 
-impl Interval {
-    fn intersection(&self, other: &Self) -> Option<Interval> {
-        let lower = cmp::max(self.lower, other.lower);
-        let upper = cmp::min(self.upper, other.upper);
-        if lower > upper {
-            None
-        } else {
-            Some(Interval{lower, upper})
-        }
-    }
-}
-
-impl PartialOrd for Interval {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.lower == other.lower && self.upper == other.upper {
-            Some(Ordering::Equal)
-        } else if self.lower >= other.lower && self.upper <= other.upper {
-            Some(Ordering::Less)
-        } else if self.lower <= other.lower && self.upper >= other.upper {
-            Some(Ordering::Greater)
-        } else {
-            None
-        }
-    }
-}
-
-impl FromStr for Interval {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bounds: Result<Vec<_>, _> = s.split("-").map(|v| v.parse::<u32>()).collect();
-        let bounds = bounds?;
-        let lower = bounds[0];
-        let upper = bounds[1];
-        Ok(Interval { lower, upper })
-    }
-}
-
-fn parse_intervals(input: &str) -> Result<Vec<(Interval, Interval)>, ParseIntError> {
+fn parse_intervals(input: &str) -> Option<Vec<(Interval<u32>, Interval<u32>)>> {
     let lines = input.lines();
     let mut intervals = Vec::new();
     for line in lines {
-        let vals: Result<Vec<Interval>, ParseIntError> =
-            line.split(",").map(|s| s.parse::<Interval>()).collect();
-        let vals = vals?;
-        intervals.push((vals[0], vals[1]));
+        let (first, second) = line.split_once(',')?;
+        intervals.push((parse_interval(first)?, parse_interval(second)?));
     }
-    Ok(intervals)
+    Some(intervals)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let intervals = parse_intervals(input).ok();
-    let intervals = intervals?;
+    let intervals = parse_intervals(input)?;
     let count = intervals
         .iter()
-        .map(|i| i.0.partial_cmp(&i.1))
-        .filter(|r| r.is_some())
+        .filter(|i| i.0.contains_interval(&i.1) || i.1.contains_interval(&i.0))
         .count();
-    Some(u32::try_from(count).ok()?)
+    u32::try_from(count).ok()
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let intervals = parse_intervals(input).ok();
-    let intervals = intervals?;
-    let count = intervals
-        .iter()
-        .map(|i| i.0.intersection(&i.1))
-        .filter(|r| r.is_some())
-        .count();
-    Some(u32::try_from(count).ok()?)
+    let intervals = parse_intervals(input)?;
+    let count = intervals.iter().filter(|i| i.0.overlaps(&i.1)).count();
+    u32::try_from(count).ok()
 }
 
 fn main() {
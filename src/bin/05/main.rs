@@ -0,0 +1,428 @@
+mod render;
+
+use advent_of_code::helpers::parse::ParseError;
+use advent_of_code::scan;
+use std::fmt;
+use std::process;
+
+type Stacks = Vec<Vec<u8>>;
+
+struct Action {
+    from: usize,
+    to: usize,
+    quantity: usize,
+}
+
+/// The action as written in the input.
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+/// Reads the drawing of the stacks, which ends with the line numbering them. Every row of
+/// crates must be as wide as the numbers, with a crate or a gap in each stack's slot.
+fn parse_stacks(input: &str) -> Result<Stacks, RearrangeError> {
+    let rows: Vec<&str> = input
+        .lines()
+        .take_while(|line| line.as_bytes().get(1) != Some(&b'1'))
+        .collect();
+    let numbers = input
+        .lines()
+        .nth(rows.len())
+        .ok_or(RearrangeError::NoStackNumbers)?;
+    let count = numbers.split_whitespace().count();
+    let mut stacks: Stacks = vec![Vec::new(); count];
+    // bottom row first, so every stack ends with its top crate.
+    for (i, row) in rows.iter().enumerate().rev() {
+        let bytes = row.as_bytes();
+        let bad_row = RearrangeError::BadRow { line: i + 1 };
+        if bytes.len() + 1 != 4 * count {
+            return Err(bad_row);
+        }
+        for (j, stack) in stacks.iter_mut().enumerate() {
+            match bytes.get(4 * j + 1) {
+                Some(&c @ b'A'..=b'Z') => stack.push(c),
+                Some(b' ') => (),
+                _ => return Err(bad_row),
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+fn parse_actions(input: &str) -> Result<Vec<Action>, RearrangeError> {
+    let mut actions: Vec<Action> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if !line.contains("move") {
+            continue;
+        }
+        let on_line = |e: ParseError| RearrangeError::BadStep(e.on_line(i + 1));
+        let (quantity, from, to) =
+            scan!(line, "move {} from {} to {}" => usize, usize, usize).map_err(on_line)?;
+        let numbered_from_one = || on_line(ParseError::new(1, "stacks are numbered from 1"));
+        let action = Action {
+            from: from.checked_sub(1).ok_or_else(numbered_from_one)?,
+            to: to.checked_sub(1).ok_or_else(numbered_from_one)?,
+            quantity,
+        };
+        actions.push(action);
+    }
+    Ok(actions)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CraneError {
+    NoSuchStack {
+        step: usize,
+        stack: usize,
+    },
+    NotEnoughCrates {
+        step: usize,
+        stack: usize,
+        wanted: usize,
+        available: usize,
+    },
+}
+
+/// Steps and stacks are numbered from 1 in messages, like in the puzzle.
+impl fmt::Display for CraneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CraneError::NoSuchStack { step, stack } => {
+                write!(f, "step {}: there is no stack {}", step + 1, stack + 1)
+            }
+            CraneError::NotEnoughCrates {
+                step,
+                stack,
+                wanted,
+                available,
+            } => write!(
+                f,
+                "step {}: cannot move {} crate(s) from stack {}, it holds {}",
+                step + 1,
+                wanted,
+                stack + 1,
+                available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CraneError {}
+
+/// Why the procedure could not be carried out, from reading the input to the last step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RearrangeError {
+    BadRow { line: usize },
+    NoStackNumbers,
+    BadStep(ParseError),
+    Crane(CraneError),
+}
+
+impl fmt::Display for RearrangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RearrangeError::BadRow { line } => write!(
+                f,
+                "line {}: expected a row of crates as wide as the stack numbers",
+                line
+            ),
+            RearrangeError::NoStackNumbers => {
+                write!(f, "the drawing has no line numbering the stacks")
+            }
+            RearrangeError::BadStep(e) => write!(f, "{}", e),
+            RearrangeError::Crane(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RearrangeError {}
+
+impl From<CraneError> for RearrangeError {
+    fn from(e: CraneError) -> Self {
+        RearrangeError::Crane(e)
+    }
+}
+
+/// A crane model. All of them lift the top crates of a stack in one go, they only differ in
+/// the order the crates land in.
+trait Crane {
+    /// Puts `lifted`, bottom crate first as it was on the stack, in the order it lands.
+    fn arrange(&self, lifted: &mut [u8]);
+
+    /// Carries out `action`, the `step`th of the procedure, leaving `stacks` untouched on error.
+    fn apply(&self, stacks: &mut Stacks, action: &Action, step: usize) -> Result<(), CraneError> {
+        for stack in [action.from, action.to] {
+            if stack >= stacks.len() {
+                return Err(CraneError::NoSuchStack { step, stack });
+            }
+        }
+        let available = stacks[action.from].len();
+        if action.quantity > available {
+            return Err(CraneError::NotEnoughCrates {
+                step,
+                stack: action.from,
+                wanted: action.quantity,
+                available,
+            });
+        }
+        // crates put back where they came from end up in their old order with either model.
+        if action.from == action.to {
+            return Ok(());
+        }
+        let mut lifted = stacks[action.from].split_off(available - action.quantity);
+        self.arrange(&mut lifted);
+        stacks[action.to].extend(lifted);
+        Ok(())
+    }
+
+    fn run(&self, stacks: &mut Stacks, actions: &[Action]) -> Result<(), CraneError> {
+        for (step, action) in actions.iter().enumerate() {
+            self.apply(stacks, action, step)?;
+        }
+        Ok(())
+    }
+}
+
+/// Moves one crate at a time, so the lifted crates land upside down.
+struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn arrange(&self, lifted: &mut [u8]) {
+        lifted.reverse();
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn arrange(&self, _lifted: &mut [u8]) {}
+}
+
+fn top_crates(stacks: &Stacks) -> String {
+    stacks
+        .iter()
+        .filter_map(|s| s.last().copied())
+        .map(char::from)
+        .collect()
+}
+
+fn rearrange(input: &str, crane: &dyn Crane) -> Result<String, RearrangeError> {
+    let mut stacks = parse_stacks(input)?;
+    let actions = parse_actions(input)?;
+    crane.run(&mut stacks, &actions)?;
+    Ok(top_crates(&stacks))
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    rearrange(input, &CrateMover9000).ok()
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    rearrange(input, &CrateMover9001).ok()
+}
+
+struct Args {
+    example: bool,
+    crane: &'static dyn Crane,
+    /// Draws the stacks after every this many steps, and at the start and the end.
+    animate: Option<usize>,
+}
+
+fn parse_crane(s: &str) -> Result<&'static dyn Crane, String> {
+    match s {
+        "9000" => Ok(&CrateMover9000),
+        "9001" => Ok(&CrateMover9001),
+        _ => Err(format!("unknown crane \"{}\", expected 9000 or 9001", s)),
+    }
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let example = args.contains(["-e", "--example"]);
+    let crane = args
+        .opt_value_from_fn("--crane", parse_crane)?
+        .unwrap_or(&CrateMover9000);
+    let every: Option<usize> = args.opt_value_from_str("--every")?;
+    let animate = if args.contains("--animate") || every.is_some() {
+        Some(every.unwrap_or(1).max(1))
+    } else {
+        None
+    };
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments {:?}", remaining),
+        });
+    }
+    Ok(Args {
+        example,
+        crane,
+        animate,
+    })
+}
+
+/// Runs the procedure, drawing the stacks before the first step, after every `every`th step
+/// and after the last one.
+fn animate(input: &str, crane: &dyn Crane, every: usize) -> Result<(), RearrangeError> {
+    let mut stacks = parse_stacks(input)?;
+    let actions = parse_actions(input)?;
+    print!("Initially:\n{}", render::render_stacks(&stacks));
+    for (step, action) in actions.iter().enumerate() {
+        crane.apply(&mut stacks, action, step)?;
+        if (step + 1) % every == 0 || step + 1 == actions.len() {
+            print!(
+                "\nAfter step {}, {}:\n{}",
+                step + 1,
+                action,
+                render::render_stacks(&stacks)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+    let folder = if args.example { "examples" } else { "inputs" };
+    let input = &advent_of_code::read_file(folder, 5);
+
+    match args.animate {
+        Some(every) => {
+            if let Err(e) = animate(input, args.crane, every) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        None => {
+            // `solve!` only says "not solved", so tell why first.
+            let cranes: [(u8, &dyn Crane); 2] = [(1, &CrateMover9000), (2, &CrateMover9001)];
+            for (part, crane) in cranes {
+                if let Err(e) = rearrange(input, crane) {
+                    eprintln!("Part {}: {}", part, e);
+                }
+            }
+            advent_of_code::solve!(1, part_one, input);
+            advent_of_code::solve!(2, part_two, input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 5);
+        assert_eq!(part_one(&input), Some(String::from("CMZ")));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 5);
+        assert_eq!(part_two(&input), Some(String::from("MCD")));
+    }
+
+    fn action(quantity: usize, from: usize, to: usize) -> Action {
+        Action { from, to, quantity }
+    }
+
+    #[test]
+    fn test_cranes() {
+        let stacks = vec![b"ABC".to_vec(), b"D".to_vec()];
+        let mut one_by_one = stacks.clone();
+        CrateMover9000
+            .run(&mut one_by_one, &[action(2, 0, 1)])
+            .unwrap();
+        assert_eq!(one_by_one, vec![b"A".to_vec(), b"DCB".to_vec()]);
+        let mut all_at_once = stacks.clone();
+        CrateMover9001
+            .run(&mut all_at_once, &[action(2, 0, 1)])
+            .unwrap();
+        assert_eq!(all_at_once, vec![b"A".to_vec(), b"DBC".to_vec()]);
+
+        let mut same = stacks.clone();
+        CrateMover9000.run(&mut same, &[action(3, 0, 0)]).unwrap();
+        assert_eq!(same, stacks);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut stacks = vec![b"AB".to_vec(), vec![]];
+        let actions = [action(1, 0, 1), action(2, 0, 1)];
+        let error = CrateMover9001.run(&mut stacks, &actions).unwrap_err();
+        assert_eq!(
+            error,
+            CraneError::NotEnoughCrates {
+                step: 1,
+                stack: 0,
+                wanted: 2,
+                available: 1
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "step 2: cannot move 2 crate(s) from stack 1, it holds 1"
+        );
+        // the failed step leaves the stacks as the first one left them.
+        assert_eq!(stacks, vec![b"A".to_vec(), b"B".to_vec()]);
+
+        let error = CrateMover9000
+            .run(&mut stacks, &[action(1, 0, 2)])
+            .unwrap_err();
+        assert_eq!(error.to_string(), "step 1: there is no stack 3");
+    }
+
+    #[test]
+    fn test_input_errors() {
+        let error = |input: &str| rearrange(input, &CrateMover9000).unwrap_err().to_string();
+        // a row trimmed short, a row wider than the stacks and a line with no slots at all.
+        assert_eq!(
+            error("    [D]\n[N] [C]    \n 1   2   3 \n"),
+            "line 1: expected a row of crates as wide as the stack numbers"
+        );
+        assert_eq!(
+            error("[N] [C]\n[Z] [M] [P]\n 1   2 \n"),
+            "line 2: expected a row of crates as wide as the stack numbers"
+        );
+        assert_eq!(
+            error("[N]\n\n 1 \n"),
+            "line 2: expected a row of crates as wide as the stack numbers"
+        );
+        assert_eq!(
+            error("[N] [c]\n 1   2 \n"),
+            "line 1: expected a row of crates as wide as the stack numbers"
+        );
+        assert_eq!(
+            error("[N] [C]\n"),
+            "the drawing has no line numbering the stacks"
+        );
+        assert_eq!(
+            error("[N] [C]\n 1   2 \n\nmove 1 from 1 to 2\nmove x from 1 to 2\n"),
+            "line 5, column 6: could not parse \"x\" as usize"
+        );
+        assert_eq!(
+            error("[N] [C]\n 1   2 \n\nmove 1 from 0 to 2\n"),
+            "line 4, column 1: stacks are numbered from 1"
+        );
+        assert_eq!(
+            error("[N] [C]\n 1   2 \n\nmove 1 from 1 to 2\nmove 3 from 1 to 2\n"),
+            "step 2: cannot move 3 crate(s) from stack 1, it holds 0"
+        );
+    }
+}
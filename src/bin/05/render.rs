@@ -0,0 +1,51 @@
+/*
+ * Draws the stacks the way the puzzle input does, so a drawing can be read back with
+ * `parse_stacks`:
+ *
+ *     [D]
+ * [N] [C]
+ * [Z] [M] [P]
+ *  1   2   3
+ *
+ * Rows keep their trailing spaces, every line is as wide as the number line.
+ */
+use crate::Stacks;
+
+pub fn render_stacks(stacks: &Stacks) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            let cells: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(&c) => format!("[{}]", c as char),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            cells.join(" ")
+        })
+        .collect();
+    let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!("{:^3}", n)).collect();
+    lines.push(numbers.join(" "));
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_stacks;
+
+    #[test]
+    fn test_render_stacks() {
+        let input = advent_of_code::read_file("examples", 5);
+        let header: String = input.split_inclusive('\n').take(4).collect();
+        let stacks = parse_stacks(&input).unwrap();
+        assert_eq!(render_stacks(&stacks), header);
+
+        let stacks = vec![vec![], b"AB".to_vec(), vec![b'C']];
+        let rendered = render_stacks(&stacks);
+        assert_eq!(rendered, "    [B]    \n    [A] [C]\n 1   2   3 \n");
+        assert_eq!(parse_stacks(&rendered), Ok(stacks));
+    }
+}
@@ -1,14 +1,8 @@
-use std::collections::HashSet;
+use advent_of_code::helpers::bitset::first_distinct_window;
 
 pub fn find_unique_idx(input: &str, win_size : usize) -> Option<u32> {
-    for (i, window) in input.as_bytes().windows(win_size).enumerate() {
-        let win_set : HashSet<u8> = HashSet::from_iter(window.iter().cloned());
-        if win_set.len() == win_size {
-            let idx : u32 = (i + win_size).try_into().ok()?;
-            return Some(idx);
-        }
-    }
-    None
+    let i = first_distinct_window(input.as_bytes(), win_size)?;
+    (i + win_size).try_into().ok()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
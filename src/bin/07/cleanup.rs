@@ -0,0 +1,284 @@
+/*
+ * Working out which directories to delete to make room for the update.
+ */
+use crate::fs::FileSystem;
+use advent_of_code::helpers::tree::NodeId;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+pub const DISK_SIZE: usize = 70_000_000;
+pub const UPDATE_SIZE: usize = 30_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// The smallest single directory that frees enough space, as the puzzle asks.
+    SingleDir,
+    /// The set of directories freeing the fewest bytes that is still enough.
+    /// Nested directories are never combined, since deleting the outer one removes both.
+    MinimalSet,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cleanup {
+    AlreadyEnoughSpace,
+    Delete { dirs: Vec<NodeId>, freed: usize },
+}
+
+impl Cleanup {
+    pub fn freed(&self) -> usize {
+        match self {
+            Cleanup::AlreadyEnoughSpace => 0,
+            Cleanup::Delete { freed, .. } => *freed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CleanupError {
+    DiskOverfull { used: usize, disk: usize },
+    UpdateTooLarge { update: usize, disk: usize },
+}
+
+impl fmt::Display for CleanupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CleanupError::DiskOverfull { used, disk } => {
+                write!(f, "{} bytes are in use on a disk of {} bytes", used, disk)
+            }
+            CleanupError::UpdateTooLarge { update, disk } => write!(
+                f,
+                "an update of {} bytes can never fit on a disk of {} bytes",
+                update, disk
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CleanupError {}
+
+pub fn plan_cleanup(
+    tree: &FileSystem,
+    disk: usize,
+    update: usize,
+    strategy: Strategy,
+) -> Result<Cleanup, CleanupError> {
+    let used = tree.used();
+    if used > disk {
+        return Err(CleanupError::DiskOverfull { used, disk });
+    }
+    if update > disk {
+        return Err(CleanupError::UpdateTooLarge { update, disk });
+    }
+    let free = disk - used;
+    if free >= update {
+        return Ok(Cleanup::AlreadyEnoughSpace);
+    }
+    let needed = update - free;
+
+    // deleting the root frees `used >= needed` bytes, so there is always a way.
+    let dirs = match strategy {
+        Strategy::SingleDir => vec![tree
+            .ids()
+            .filter(|&id| tree[id].is_dir() && tree.size(id) >= needed)
+            .min_by_key(|&id| tree.size(id))
+            .expect("the root is always big enough")],
+        Strategy::MinimalSet => minimal_set(tree, needed),
+    };
+    let freed = dirs.iter().map(|&id| tree.size(id)).sum();
+    Ok(Cleanup::Delete { dirs, freed })
+}
+
+/// Branch and bound over the directories in pre-order. Position `i` means "directories before `i`
+/// are decided": skipping directory `i` moves to `i + 1`, taking it jumps past its whole subtree.
+/// A branch stops once it frees `needed`, when even the outermost directories left cannot make up
+/// the rest, or when it would free more than the best set so far. Branches meeting at the same
+/// position with the same sum go the same way, so each is only followed once. Nothing here
+/// grows with the number of bytes, only with the number of directories and the sums reached.
+fn minimal_set(tree: &FileSystem, needed: usize) -> Vec<NodeId> {
+    let dirs: Vec<NodeId> = tree
+        .pre_order(tree.root())
+        .filter(|&id| tree[id].is_dir())
+        .collect();
+    let position: HashMap<NodeId, usize> =
+        dirs.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    // the position just past the last directory in each directory's subtree.
+    let mut end = vec![0; dirs.len()];
+    for (i, &id) in dirs.iter().enumerate().rev() {
+        end[i] = tree
+            .children(id)
+            .iter()
+            .filter_map(|c| position.get(c))
+            .map(|&c| end[c])
+            .max()
+            .unwrap_or(i + 1);
+    }
+    // the most that can still be freed from position `i` on, by taking the outermost directories.
+    let mut most = vec![0; dirs.len() + 1];
+    for i in (0..dirs.len()).rev() {
+        most[i] = tree.size(dirs[i]) + most[end[i]];
+    }
+
+    // every directory taken, as (position, the one taken before it).
+    let mut taken: Vec<(usize, Option<usize>)> = Vec::new();
+    let mut best: Option<(usize, Option<usize>)> = None;
+    let mut seen = HashSet::new();
+    // (position, sum so far, the last directory taken)
+    let mut stack = vec![(0, 0, None)];
+    while let Some((i, sum, last)) = stack.pop() {
+        if sum >= needed {
+            if best.map_or(true, |(b, _)| sum < b) {
+                best = Some((sum, last));
+            }
+            continue;
+        }
+        if sum + most[i] < needed || !seen.insert((i, sum)) {
+            continue;
+        }
+        stack.push((i + 1, sum, last));
+        let with = sum + tree.size(dirs[i]);
+        if best.map_or(true, |(b, _)| with < b) {
+            taken.push((i, last));
+            stack.push((end[i], with, Some(taken.len() - 1)));
+        }
+        if best.is_some_and(|(b, _)| b == needed) {
+            break;
+        }
+    }
+
+    let (_, mut last) = best.expect("the root is always big enough");
+    let mut chosen = Vec::new();
+    while let Some(t) = last {
+        chosen.push(dirs[taken[t].0]);
+        last = taken[t].1;
+    }
+    chosen.reverse();
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::{self, Entry};
+    use crate::shell::parse_tree;
+    use advent_of_code::helpers::rng::XorShift;
+
+    fn plan(input: &str, disk: usize, update: usize, strategy: Strategy) -> (Vec<String>, usize) {
+        let tree = parse_tree(input).unwrap();
+        match plan_cleanup(&tree, disk, update, strategy).unwrap() {
+            Cleanup::AlreadyEnoughSpace => (vec![], 0),
+            Cleanup::Delete { dirs, freed } => (fs::paths(&tree, &dirs), freed),
+        }
+    }
+
+    #[test]
+    fn test_example() {
+        let input = advent_of_code::read_file("examples", 7);
+        for strategy in [Strategy::SingleDir, Strategy::MinimalSet] {
+            assert_eq!(
+                plan(&input, DISK_SIZE, UPDATE_SIZE, strategy),
+                (vec!["/d".to_string()], 24933642)
+            );
+        }
+        assert_eq!(
+            plan(&input, 100_000_000, UPDATE_SIZE, Strategy::SingleDir),
+            (vec![], 0)
+        );
+    }
+
+    #[test]
+    fn test_minimal_set() {
+        // / (16) contains p (10), q (5) and a file of 1; p contains c (4) and a file of 6.
+        let input = "$ ls\ndir p\ndir q\n1 e\n$ cd p\n$ ls\ndir c\n6 f\n$ cd c\n$ ls\n4 g\n\
+                     $ cd /q\n$ ls\n5 h";
+        // 14 bytes needed: p and c together would be exactly enough, but c is inside p.
+        assert_eq!(
+            plan(input, 100, 98, Strategy::MinimalSet),
+            (vec!["/p".to_string(), "/q".to_string()], 15)
+        );
+        // 9 bytes needed: c and q beat the single smallest directory p.
+        assert_eq!(
+            plan(input, 100, 93, Strategy::MinimalSet),
+            (vec!["/p/c".to_string(), "/q".to_string()], 9)
+        );
+        assert_eq!(
+            plan(input, 100, 93, Strategy::SingleDir),
+            (vec!["/p".to_string()], 10)
+        );
+        // the same tree in terabytes: the search does not depend on the number of bytes.
+        let tera = 1_000_000_000_000;
+        let input = input
+            .lines()
+            .map(|line| match line.split_once(' ') {
+                Some((size, name)) if size.parse::<usize>().is_ok() => {
+                    format!("{}000000000000 {}\n", size, name)
+                }
+                _ => format!("{}\n", line),
+            })
+            .collect::<String>();
+        assert_eq!(
+            plan(&input, 100 * tera, 93 * tera, Strategy::MinimalSet),
+            (vec!["/p/c".to_string(), "/q".to_string()], 9 * tera)
+        );
+    }
+
+    #[test]
+    fn test_minimal_set_matches_brute_force() {
+        let mut rng = XorShift::default();
+        for _ in 0..200 {
+            let mut tree = fs::empty();
+            let mut dirs = vec![tree.root()];
+            for n in 0..rng.below(10) {
+                let parent = dirs[rng.below(dirs.len())];
+                let name = n.to_string();
+                if rng.below(3) == 0 {
+                    let size = 1 + rng.below(20);
+                    tree.add_child(parent, Entry::File { size, name });
+                } else {
+                    dirs.push(tree.add_child(parent, Entry::Dir { name }));
+                }
+            }
+            let tree = FileSystem::new(tree);
+            if tree.used() == 0 {
+                continue;
+            }
+            let needed = 1 + rng.below(tree.used());
+
+            let nested = |a: NodeId, b: NodeId| tree.ancestors(b).any(|x| x == a);
+            let brute_force = (1..1usize << dirs.len())
+                .map(|mask| {
+                    let set: Vec<NodeId> = (0..dirs.len())
+                        .filter(|i| mask & (1 << i) != 0)
+                        .map(|i| dirs[i])
+                        .collect();
+                    set
+                })
+                .filter(|set| set.iter().all(|&a| set.iter().all(|&b| !nested(a, b))))
+                .map(|set| set.iter().map(|&id| tree.size(id)).sum::<usize>())
+                .filter(|&total| total >= needed)
+                .min();
+
+            let chosen = minimal_set(&tree, needed);
+            assert!(chosen
+                .iter()
+                .all(|&a| chosen.iter().all(|&b| !nested(a, b))));
+            let total = chosen.iter().map(|&id| tree.size(id)).sum::<usize>();
+            assert_eq!(Some(total), brute_force);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let tree = parse_tree("$ ls\n10 a").unwrap();
+        assert_eq!(
+            plan_cleanup(&tree, 5, 1, Strategy::SingleDir),
+            Err(CleanupError::DiskOverfull { used: 10, disk: 5 })
+        );
+        assert_eq!(
+            plan_cleanup(&tree, 20, 30, Strategy::SingleDir),
+            Err(CleanupError::UpdateTooLarge {
+                update: 30,
+                disk: 20
+            })
+        );
+    }
+}
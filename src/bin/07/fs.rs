@@ -0,0 +1,139 @@
+/*
+ * The file system seen in the terminal output: a tree of directories and sized files.
+ */
+use advent_of_code::helpers::tree::{Named, NodeId, Tree};
+use std::ops::Deref;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    File { size: usize, name: String },
+    Dir { name: String },
+}
+
+impl Entry {
+    pub fn is_dir(&self) -> bool {
+        matches!(self, Entry::Dir { .. })
+    }
+}
+
+impl Named for Entry {
+    fn name(&self) -> &str {
+        match self {
+            Entry::File { name, .. } => name,
+            Entry::Dir { name } => name,
+        }
+    }
+}
+
+pub type EntryTree = Tree<Entry>;
+
+pub fn empty() -> EntryTree {
+    Tree::new(Entry::Dir {
+        name: "/".to_string(),
+    })
+}
+
+/// The absolute path of `id`, e.g. `/a/e/i`.
+pub fn path(tree: &EntryTree, id: NodeId) -> String {
+    let mut names: Vec<&str> = std::iter::once(id)
+        .chain(tree.ancestors(id))
+        .map(|a| tree[a].name())
+        .collect();
+    names.pop();
+    names.reverse();
+    format!("/{}", names.join("/"))
+}
+
+/// Visits every node in pre-order with its depth and absolute path. The path is kept in one
+/// buffer, cut back to the parent's path before each step down, so each node costs only its name.
+pub fn walk(tree: &EntryTree, mut visit: impl FnMut(NodeId, usize, &str)) {
+    let mut stack = vec![(tree.root(), 0)];
+    let mut path = String::new();
+    // ends[d] is the length of the path of the directory open at depth d.
+    let mut ends: Vec<usize> = Vec::new();
+    while let Some((id, depth)) = stack.pop() {
+        ends.truncate(depth);
+        path.truncate(ends.last().copied().unwrap_or(0));
+        if depth > 0 {
+            path.push('/');
+            path.push_str(tree[id].name());
+        }
+        ends.push(path.len());
+        visit(id, depth, if depth == 0 { "/" } else { &path });
+        stack.extend(tree.children(id).iter().rev().map(|&c| (c, depth + 1)));
+    }
+}
+
+/// The absolute paths of `ids`, in the same order, from a single walk over the tree.
+pub fn paths(tree: &EntryTree, ids: &[NodeId]) -> Vec<String> {
+    let mut wanted: Vec<Vec<usize>> = vec![Vec::new(); tree.len()];
+    for (i, &id) in ids.iter().enumerate() {
+        wanted[id].push(i);
+    }
+    let mut paths = vec![String::new(); ids.len()];
+    walk(tree, |id, _, path| {
+        for &i in &wanted[id] {
+            paths[i] = path.to_string();
+        }
+    });
+    paths
+}
+
+/// A finished tree together with the total size of every node, worked out once, bottom-up,
+/// when it is built. It only hands out shared references to the tree, so the sizes stay right.
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    tree: EntryTree,
+    sizes: Vec<usize>,
+}
+
+impl FileSystem {
+    pub fn new(tree: EntryTree) -> Self {
+        let sizes = tree.fold_up(|entry, children| match entry {
+            Entry::File { size, .. } => *size,
+            Entry::Dir { .. } => children.sum(),
+        });
+        Self { tree, sizes }
+    }
+
+    /// The size of a file, or the total size of everything inside a directory.
+    pub fn size(&self, id: NodeId) -> usize {
+        self.sizes[id]
+    }
+
+    /// The total size of everything on the disk.
+    pub fn used(&self) -> usize {
+        self.size(self.tree.root())
+    }
+}
+
+impl Deref for FileSystem {
+    type Target = EntryTree;
+
+    fn deref(&self) -> &EntryTree {
+        &self.tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::parse_tree;
+
+    #[test]
+    fn test_walk_and_paths() {
+        let tree = parse_tree(&advent_of_code::read_file("examples", 7)).unwrap();
+        let mut visited = Vec::new();
+        walk(&tree, |id, depth, path| {
+            assert_eq!(depth, tree.depth(id));
+            assert_eq!(path, super::path(&tree, id));
+            visited.push(id);
+        });
+        assert_eq!(visited, tree.pre_order(tree.root()).collect::<Vec<_>>());
+        let e = tree.lookup(tree.root(), "/a/e").unwrap();
+        assert_eq!(
+            paths(&tree, &[e, tree.root(), e]),
+            vec!["/a/e", "/", "/a/e"]
+        );
+    }
+}
@@ -0,0 +1,465 @@
+/*
+ * The file system as nested JSON, for diffing trees and writing fixtures by hand:
+ * `{"name": "/", "type": "dir", "size": 48381165, "children": [...]}`.
+ * A session can nest directories far deeper than a recursive serializer or parser copes with,
+ * so both directions keep their own stack. serde_json only escapes and unescapes the names.
+ */
+use crate::fs::{self, Entry, FileSystem};
+use crate::query::Kind;
+use advent_of_code::helpers::parse::ParseError;
+use advent_of_code::helpers::tree::Named;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum JsonError {
+    Syntax(ParseError),
+    InvalidRoot,
+    InvalidName(String),
+    Duplicate(String),
+    MissingSize(String),
+    FileWithChildren(String),
+    SizeMismatch {
+        path: String,
+        listed: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Syntax(e) => write!(f, "{}", e),
+            JsonError::InvalidRoot => write!(f, "the root must be a directory named \"/\""),
+            JsonError::InvalidName(path) => write!(f, "\"{}\" is not a valid name", path),
+            JsonError::Duplicate(path) => write!(f, "\"{}\" appears more than once", path),
+            JsonError::MissingSize(path) => write!(f, "file \"{}\" has no size", path),
+            JsonError::FileWithChildren(path) => write!(f, "file \"{}\" has children", path),
+            JsonError::SizeMismatch {
+                path,
+                listed,
+                actual,
+            } => write!(
+                f,
+                "directory \"{}\" is listed with {} bytes but holds {}",
+                path, listed, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// JSON for the whole tree, with the total size of every directory. Every entry starts a new
+/// line and the brackets closing a directory follow its last entry, so the output stays
+/// proportional to the tree however deep it is.
+pub fn to_json(tree: &FileSystem) -> String {
+    let mut out = String::new();
+    let mut stack = vec![tree.root()];
+    while let Some(id) = stack.pop() {
+        let (kind, size) = match &tree[id] {
+            Entry::File { size, .. } => ("file", *size),
+            Entry::Dir { .. } => ("dir", tree.size(id)),
+        };
+        let name = serde_json::to_string(tree[id].name()).expect("a name always serializes");
+        out.push_str(&format!(
+            "{{\"name\": {}, \"type\": \"{}\", \"size\": {}",
+            name, kind, size
+        ));
+        let children = tree.children(id);
+        if !children.is_empty() {
+            out.push_str(", \"children\": [\n");
+            stack.extend(children.iter().rev());
+            continue;
+        }
+        if tree[id].is_dir() {
+            out.push_str(", \"children\": []");
+        }
+        out.push('}');
+        // close every directory this entry was the last one in.
+        let mut current = id;
+        while let Some(parent) = tree.parent(current) {
+            if tree.children(parent).last() != Some(&current) {
+                out.push_str(",\n");
+                break;
+            }
+            out.push_str("]}");
+            current = parent;
+        }
+    }
+    out
+}
+
+/// An object read from the JSON, before it is checked and added to the tree.
+#[derive(Debug, Default)]
+struct Object {
+    /// Where the object starts, for errors about fields it lacks.
+    offset: usize,
+    name: Option<String>,
+    kind: Option<Kind>,
+    size: Option<usize>,
+    /// Indices into the list of objects.
+    children: Option<Vec<usize>>,
+}
+
+/// A reader for the subset of JSON `to_json` writes: objects, arrays of objects, strings and
+/// unsigned integers, with any whitespace between them.
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn error_at(&self, offset: usize, message: impl Into<String>) -> JsonError {
+        let before = &self.input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        JsonError::Syntax(ParseError::new(column, message).on_line(line))
+    }
+
+    fn error(&self, message: impl Into<String>) -> JsonError {
+        self.error_at(self.pos, message)
+    }
+
+    /// Moves past every byte from the current one on that passes `f`.
+    fn skip_while(&mut self, f: impl Fn(u8) -> bool) {
+        let bytes = self.input.as_bytes();
+        while self.pos < bytes.len() && f(bytes[self.pos]) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.skip_while(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'));
+    }
+
+    /// Skips whitespace, then consumes `c` if it comes next.
+    fn eat(&mut self, c: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.input.as_bytes().get(self.pos) == Some(&c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), JsonError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", c as char)))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let bytes = self.input.as_bytes();
+        if bytes.get(start) != Some(&b'"') {
+            return Err(self.error("expected a string"));
+        }
+        let mut end = start + 1;
+        loop {
+            match bytes.get(end) {
+                Some(b'"') => break,
+                Some(b'\\') => end += 2,
+                Some(_) => end += 1,
+                None => return Err(self.error_at(start, "unterminated string")),
+            }
+        }
+        self.pos = end + 1;
+        let raw = &self.input[start + 1..end];
+        if !raw.bytes().any(|b| b == b'\\' || b < 0x20) {
+            return Ok(raw.to_string());
+        }
+        serde_json::from_str(&self.input[start..=end])
+            .map_err(|e| self.error_at(start, format!("invalid string: {}", e)))
+    }
+
+    fn integer(&mut self) -> Result<usize, JsonError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        self.skip_while(|b| b.is_ascii_digit());
+        self.input[start..self.pos]
+            .parse()
+            .map_err(|_| self.error_at(start, "expected a size in bytes"))
+    }
+
+    /// Reads the root object and everything in it, in the order the objects start.
+    fn objects(&mut self) -> Result<Vec<Object>, JsonError> {
+        enum Open {
+            Object(usize),
+            Children(usize),
+        }
+        self.skip_whitespace();
+        let mut objects = vec![Object {
+            offset: self.pos,
+            ..Object::default()
+        }];
+        self.expect(b'{')?;
+        // what is open, and whether anything has been read in it yet.
+        let mut stack = vec![(Open::Object(0), false)];
+        while let Some((open, started)) = stack.last_mut() {
+            let closing = match open {
+                Open::Object(_) => b'}',
+                Open::Children(_) => b']',
+            };
+            if self.eat(closing) {
+                stack.pop();
+                continue;
+            }
+            if *started {
+                self.expect(b',')?;
+            }
+            *started = true;
+            match *open {
+                Open::Object(i) => {
+                    self.skip_whitespace();
+                    let offset = self.pos;
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    let object = &objects[i];
+                    let repeated = match key.as_str() {
+                        "name" => object.name.is_some(),
+                        "type" => object.kind.is_some(),
+                        "size" => object.size.is_some(),
+                        "children" => object.children.is_some(),
+                        _ => return Err(self.error_at(offset, format!("unknown field {:?}", key))),
+                    };
+                    if repeated {
+                        return Err(self.error_at(offset, format!("repeated field {:?}", key)));
+                    }
+                    match key.as_str() {
+                        "name" => objects[i].name = Some(self.string()?),
+                        "type" => {
+                            self.skip_whitespace();
+                            let offset = self.pos;
+                            objects[i].kind = Some(match self.string()?.as_str() {
+                                "file" => Kind::File,
+                                "dir" => Kind::Dir,
+                                _ => {
+                                    return Err(
+                                        self.error_at(offset, "expected \"file\" or \"dir\"")
+                                    )
+                                }
+                            });
+                        }
+                        "size" => objects[i].size = Some(self.integer()?),
+                        _ => {
+                            self.expect(b'[')?;
+                            objects[i].children = Some(Vec::new());
+                            stack.push((Open::Children(i), false));
+                        }
+                    }
+                }
+                Open::Children(parent) => {
+                    self.skip_whitespace();
+                    let child = objects.len();
+                    objects.push(Object {
+                        offset: self.pos,
+                        ..Object::default()
+                    });
+                    self.expect(b'{')?;
+                    objects[parent]
+                        .children
+                        .as_mut()
+                        .expect("the list is open")
+                        .push(child);
+                    stack.push((Open::Object(child), false));
+                }
+            }
+        }
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return Err(self.error("unexpected trailing input"));
+        }
+        for object in &objects {
+            if object.name.is_none() {
+                return Err(self.error_at(object.offset, "missing field \"name\""));
+            }
+            if object.kind.is_none() {
+                return Err(self.error_at(object.offset, "missing field \"type\""));
+            }
+        }
+        Ok(objects)
+    }
+}
+
+/// Reads a tree written by `to_json`. Names must be usable in the terminal session,
+/// so they cannot be empty, `.`, `..` or contain a `/` or a line break.
+pub fn from_json(json: &str) -> Result<FileSystem, JsonError> {
+    let mut objects = Reader {
+        input: json,
+        pos: 0,
+    }
+    .objects()?;
+    if objects[0].kind != Some(Kind::Dir) || objects[0].name.as_deref() != Some("/") {
+        return Err(JsonError::InvalidRoot);
+    }
+    let mut tree = fs::empty();
+    let mut listed = vec![];
+    let mut stack = vec![(tree.root(), 0)];
+    while let Some((id, dir)) = stack.pop() {
+        if let Some(size) = objects[dir].size {
+            listed.push((id, size));
+        }
+        for child in objects[dir].children.take().unwrap_or_default() {
+            let object = &mut objects[child];
+            let name = object.name.take().expect("every object has a name");
+            // the path is only worked out for an error, so deep trees stay linear.
+            let path = || format!("{}/{}", fs::path(&tree, id).trim_end_matches('/'), name);
+            if matches!(name.as_str(), "" | "." | "..") || name.contains(['/', '\n', '\r']) {
+                return Err(JsonError::InvalidName(path()));
+            }
+            if tree.find_child(id, &name).is_some() {
+                return Err(JsonError::Duplicate(path()));
+            }
+            match object.kind.expect("every object has a type") {
+                Kind::File => {
+                    if object.children.is_some() {
+                        return Err(JsonError::FileWithChildren(path()));
+                    }
+                    let size = object.size.ok_or_else(|| JsonError::MissingSize(path()))?;
+                    tree.add_child(id, Entry::File { size, name });
+                }
+                Kind::Dir => {
+                    let child_id = tree.add_child(id, Entry::Dir { name });
+                    stack.push((child_id, child));
+                }
+            }
+        }
+    }
+
+    let tree = FileSystem::new(tree);
+    for (id, listed) in listed {
+        if tree.size(id) != listed {
+            return Err(JsonError::SizeMismatch {
+                path: fs::path(&tree, id),
+                listed,
+                actual: tree.size(id),
+            });
+        }
+    }
+    Ok(tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::{parse_tree, to_log};
+
+    #[test]
+    fn test_round_trip() {
+        let tree = parse_tree(&advent_of_code::read_file("examples", 7)).unwrap();
+        let json = to_json(&tree);
+        let lines: Vec<&str> = json.lines().collect();
+        assert_eq!(lines.len(), 14);
+        assert_eq!(
+            lines[0],
+            r#"{"name": "/", "type": "dir", "size": 48381165, "children": ["#
+        );
+        assert_eq!(lines[3], r#"{"name": "i", "type": "file", "size": 584}]},"#);
+        assert_eq!(
+            lines[13],
+            r#"{"name": "k", "type": "file", "size": 7214296}]}]}"#
+        );
+        let imported = from_json(&json).unwrap();
+        assert_eq!(to_log(&imported), to_log(&tree));
+        assert_eq!(to_json(&imported), json);
+    }
+
+    #[test]
+    fn test_deep_round_trip() {
+        // far past the nesting serde_json reads by default.
+        let depth = 50_000;
+        let tree = parse_tree(&crate::deep_log(depth)).unwrap();
+        let json = to_json(&tree);
+        assert!(json.len() < 200 * depth);
+        let imported = from_json(&json).unwrap();
+        assert_eq!(imported.used(), depth);
+        assert_eq!(to_json(&imported), json);
+    }
+
+    #[test]
+    fn test_hand_written() {
+        // directory sizes and empty children lists may be left out.
+        let json = r#"{"name": "/", "type": "dir", "children": [
+            {"name": "a", "type": "dir", "children": [{"name": "x", "type": "file", "size": 3}]},
+            {"name": "b", "type": "dir"},
+            {"name": "c.txt", "type": "file", "size": 4}
+        ]}"#;
+        let tree = from_json(json).unwrap();
+        assert_eq!(
+            to_log(&tree),
+            "$ cd /\n$ ls\ndir a\ndir b\n4 c.txt\n$ cd /a\n$ ls\n3 x\n"
+        );
+        let spaced = r#"{"name": "/", "type": "dir", "children": [
+            {"name": "a b", "type": "dir", "children": [{"name": "x", "type": "file", "size": 3}]}
+        ]}"#;
+        assert_eq!(
+            to_log(&from_json(spaced).unwrap()),
+            "$ cd /\n$ ls\ndir a b\n$ cd /a b\n$ ls\n3 x\n"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |json: &str| from_json(json).unwrap_err().to_string();
+        let root = |children: &str| {
+            format!(
+                r#"{{"name": "/", "type": "dir", "children": [{}]}}"#,
+                children
+            )
+        };
+        assert!(matches!(from_json("{"), Err(JsonError::Syntax(_))));
+        assert_eq!(
+            error("{\"name\": \"/\",\n \"type\": \"dir\" \"size\": 1}"),
+            "line 2, column 16: expected ','"
+        );
+        assert_eq!(
+            error(r#"{"name": "/", "type": "dir", "kind": "x"}"#),
+            "line 1, column 30: unknown field \"kind\""
+        );
+        assert_eq!(
+            error(&root(r#"{"name": "a"}"#)),
+            "line 1, column 43: missing field \"type\""
+        );
+        assert_eq!(
+            error(&root(r#"{"name": "a", "type": "file", "size": -1}"#)),
+            "line 1, column 81: expected a size in bytes"
+        );
+        assert_eq!(
+            error(r#"{"name": "/", "type": "dir"} {}"#),
+            "line 1, column 30: unexpected trailing input"
+        );
+        assert!(matches!(
+            from_json(r#"{"name": "x", "type": "dir"}"#),
+            Err(JsonError::InvalidRoot)
+        ));
+        assert_eq!(
+            error(&root(r#"{"name": "a/b", "type": "dir"}"#)),
+            "\"/a/b\" is not a valid name"
+        );
+        assert_eq!(
+            error(&root(
+                r#"{"name": "a", "type": "dir"}, {"name": "a", "type": "file", "size": 1}"#
+            )),
+            "\"/a\" appears more than once"
+        );
+        assert_eq!(
+            error(&root(r#"{"name": "a", "type": "file"}"#)),
+            "file \"/a\" has no size"
+        );
+        assert_eq!(
+            error(&root(
+                r#"{"name": "a", "type": "file", "size": 1, "children": []}"#
+            )),
+            "file \"/a\" has children"
+        );
+        assert_eq!(
+            error(&root(
+                r#"{"name": "a", "type": "dir", "size": 2, "children": [{"name": "x", "type": "file", "size": 3}]}"#
+            )),
+            "directory \"/a\" is listed with 2 bytes but holds 3"
+        );
+    }
+}
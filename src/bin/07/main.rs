@@ -0,0 +1,240 @@
+mod cleanup;
+mod fs;
+mod json;
+mod query;
+mod shell;
+
+use cleanup::{plan_cleanup, Cleanup, Strategy};
+use fs::FileSystem;
+use query::Query;
+use shell::parse_tree;
+use std::process;
+
+/// The total size of the directories of at most 100000 bytes.
+fn small_dirs_total(tree: &FileSystem) -> Option<u32> {
+    let sum: usize = tree
+        .iter()
+        .filter(|(_, entry)| entry.is_dir())
+        .map(|(id, _)| tree.size(id))
+        .filter(|s| s <= &100000)
+        .sum();
+    let sum: u32 = sum.try_into().ok()?;
+    Some(sum)
+}
+
+/// The size of the smallest directory that makes room for the update.
+fn single_dir_to_delete(tree: &FileSystem) -> Option<u32> {
+    let cleanup = plan_cleanup(
+        tree,
+        cleanup::DISK_SIZE,
+        cleanup::UPDATE_SIZE,
+        Strategy::SingleDir,
+    )
+    .ok()?;
+    cleanup.freed().try_into().ok()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    small_dirs_total(&parse_tree(input).ok()?)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    single_dir_to_delete(&parse_tree(input).ok()?)
+}
+
+/// A session `depth` directories deep, each holding a file of 1 byte and the next directory.
+#[cfg(test)]
+fn deep_log(depth: usize) -> String {
+    "$ ls\ndir x\n1 f\n$ cd x\n".repeat(depth)
+}
+
+enum Command {
+    Tree,
+    Json,
+    Log,
+    Du {
+        limit: Option<usize>,
+    },
+    Find(Query),
+    Cleanup {
+        disk: usize,
+        update: usize,
+        strategy: Strategy,
+    },
+}
+
+struct Args {
+    example: bool,
+    /// Reads the file system from a JSON export instead of the terminal output.
+    from_json: Option<String>,
+    /// Solves the puzzle when no command is given.
+    command: Option<Command>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let example = args.contains(["-e", "--example"]);
+    let from_json = args.opt_value_from_str("--from-json")?;
+    let command = match args.subcommand()?.as_deref() {
+        None => None,
+        Some("tree") => Some(Command::Tree),
+        Some("json") => Some(Command::Json),
+        Some("log") => Some(Command::Log),
+        Some("du") => Some(Command::Du {
+            limit: args.opt_value_from_str(["-n", "--limit"])?,
+        }),
+        Some("find") => Some(Command::Find(Query {
+            name: args.opt_value_from_str("--name")?,
+            size: args.opt_value_from_str("--size")?,
+            kind: args.opt_value_from_str("--type")?,
+        })),
+        Some("cleanup") => Some(Command::Cleanup {
+            disk: args
+                .opt_value_from_str("--disk-size")?
+                .unwrap_or(cleanup::DISK_SIZE),
+            update: args
+                .opt_value_from_str("--update-size")?
+                .unwrap_or(cleanup::UPDATE_SIZE),
+            strategy: if args.contains("--set") {
+                Strategy::MinimalSet
+            } else {
+                Strategy::SingleDir
+            },
+        }),
+        Some(other) => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: format!(
+                    "unknown command \"{}\", expected tree, json, log, du, find or cleanup",
+                    other
+                ),
+            })
+        }
+    };
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments {:?}", remaining),
+        });
+    }
+    Ok(Args {
+        example,
+        from_json,
+        command,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+    let tree = match &args.from_json {
+        Some(path) => match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| json::from_json(&json).map_err(|e| e.to_string()))
+        {
+            Ok(tree) => tree,
+            Err(e) => {
+                eprintln!("Failed to import {}: {}", path, e);
+                process::exit(1);
+            }
+        },
+        None => {
+            let folder = if args.example { "examples" } else { "inputs" };
+            let input = &advent_of_code::read_file(folder, 7);
+            if args.command.is_none() {
+                advent_of_code::solve!(1, part_one, input);
+                advent_of_code::solve!(2, part_two, input);
+                return;
+            }
+            match parse_tree(input) {
+                Ok(tree) => tree,
+                Err(e) => {
+                    eprintln!("Failed to read the terminal output: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+    };
+
+    let Some(command) = args.command else {
+        let answer =
+            |answer: Option<u32>| answer.map_or("not solved".to_string(), |a| a.to_string());
+        println!("Small directories: {}", answer(small_dirs_total(&tree)));
+        println!(
+            "Directory to delete: {}",
+            answer(single_dir_to_delete(&tree))
+        );
+        return;
+    };
+    match command {
+        Command::Tree => print!("{}", query::render_tree(&tree)),
+        Command::Json => println!("{}", json::to_json(&tree)),
+        Command::Log => print!("{}", shell::to_log(&tree)),
+        Command::Du { limit } => {
+            let dirs = query::du(&tree);
+            for (path, size) in dirs.iter().take(limit.unwrap_or(dirs.len())) {
+                println!("{:>10}  {}", size, path);
+            }
+        }
+        Command::Find(query) => {
+            for path in query::find(&tree, &query) {
+                println!("{}", path);
+            }
+        }
+        Command::Cleanup {
+            disk,
+            update,
+            strategy,
+        } => match plan_cleanup(&tree, disk, update, strategy) {
+            Ok(Cleanup::AlreadyEnoughSpace) => {
+                println!("There is already enough free space, nothing to delete.")
+            }
+            Ok(Cleanup::Delete { dirs, freed }) => {
+                for (&id, path) in dirs.iter().zip(fs::paths(&tree, &dirs)) {
+                    println!("{:>10}  {}", tree.size(id), path);
+                }
+                println!(
+                    "Deleting {} director(ies) frees {} bytes.",
+                    dirs.len(),
+                    freed
+                );
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 7);
+        assert_eq!(part_one(&input), Some(95437));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 7);
+        assert_eq!(part_two(&input), Some(24933642));
+    }
+
+    #[test]
+    fn test_deeply_nested() {
+        let depth = 50_000;
+        let input = deep_log(depth);
+        let tree = parse_tree(&input).unwrap();
+        assert_eq!(tree.used(), depth);
+        // every directory is small enough to count, from 50000 bytes down to the empty innermost one.
+        let expected = (depth + 1) * depth / 2;
+        assert_eq!(part_one(&input), Some(expected as u32));
+    }
+}
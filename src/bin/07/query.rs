@@ -0,0 +1,225 @@
+/*
+ * Questions beyond the puzzle itself: `tree`, `du` and `find` style views of the file system.
+ */
+use crate::fs::{self, Entry, FileSystem};
+use advent_of_code::helpers::tree::Named;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Renders the tree like the puzzle description does, with sizes for directories too:
+/// `- / (dir, size=48381165)`, indented two spaces per level.
+pub fn render_tree(tree: &FileSystem) -> String {
+    let mut out = String::new();
+    fs::walk(tree, |id, depth, _| {
+        let kind = if tree[id].is_dir() { "dir" } else { "file" };
+        writeln!(
+            out,
+            "{}- {} ({}, size={})",
+            "  ".repeat(depth),
+            tree[id].name(),
+            kind,
+            tree.size(id)
+        )
+        .unwrap();
+    });
+    out
+}
+
+/// Every directory with its total size, largest first, ties ordered by path.
+pub fn du(tree: &FileSystem) -> Vec<(String, usize)> {
+    let mut dirs = Vec::new();
+    fs::walk(tree, |id, _, path| {
+        if tree[id].is_dir() {
+            dirs.push((path.to_string(), tree.size(id)));
+        }
+    });
+    dirs.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    dirs
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    File,
+    Dir,
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "f" | "file" => Ok(Kind::File),
+            "d" | "dir" => Ok(Kind::Dir),
+            _ => Err(format!("unknown type \"{}\", expected f or d", s)),
+        }
+    }
+}
+
+/// A size test in the style of `find -size`: `+N` is more than `N`, `-N` less than `N`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeFilter {
+    Exactly(usize),
+    Above(usize),
+    Below(usize),
+}
+
+impl SizeFilter {
+    pub fn matches(self, size: usize) -> bool {
+        match self {
+            SizeFilter::Exactly(n) => size == n,
+            SizeFilter::Above(n) => size > n,
+            SizeFilter::Below(n) => size < n,
+        }
+    }
+}
+
+impl FromStr for SizeFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let invalid = |_| format!("invalid size \"{}\", expected N, +N or -N", s);
+        if let Some(n) = s.strip_prefix('+') {
+            n.parse().map(SizeFilter::Above).map_err(invalid)
+        } else if let Some(n) = s.strip_prefix('-') {
+            n.parse().map(SizeFilter::Below).map_err(invalid)
+        } else {
+            s.parse().map(SizeFilter::Exactly).map_err(invalid)
+        }
+    }
+}
+
+/// Matches `name` against a glob where `*` is any run of characters and `?` any one character.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // where the last `*` was and how much of the name it has swallowed so far.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub name: Option<String>,
+    pub size: Option<SizeFilter>,
+    pub kind: Option<Kind>,
+}
+
+impl Query {
+    fn matches(&self, entry: &Entry, size: usize) -> bool {
+        let kind = if entry.is_dir() {
+            Kind::Dir
+        } else {
+            Kind::File
+        };
+        self.name
+            .as_ref()
+            .map_or(true, |pattern| glob_match(pattern, entry.name()))
+            && self.size.map_or(true, |filter| filter.matches(size))
+            && self.kind.map_or(true, |k| k == kind)
+    }
+}
+
+/// The paths of the entries matching every part of `query`, in listing order.
+pub fn find(tree: &FileSystem, query: &Query) -> Vec<String> {
+    let mut found = Vec::new();
+    fs::walk(tree, |id, _, path| {
+        if query.matches(&tree[id], tree.size(id)) {
+            found.push(path.to_string());
+        }
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::parse_tree;
+
+    fn example() -> FileSystem {
+        parse_tree(&advent_of_code::read_file("examples", 7)).unwrap()
+    }
+
+    #[test]
+    fn test_render_tree() {
+        let tree = example();
+        let rendered = render_tree(&tree);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 14);
+        assert_eq!(lines[0], "- / (dir, size=48381165)");
+        assert_eq!(lines[2], "    - e (dir, size=584)");
+        assert_eq!(lines[3], "      - i (file, size=584)");
+        assert_eq!(lines[13], "    - k (file, size=7214296)");
+    }
+
+    #[test]
+    fn test_du() {
+        let tree = example();
+        assert_eq!(
+            du(&tree),
+            vec![
+                ("/".to_string(), 48381165),
+                ("/d".to_string(), 24933642),
+                ("/a".to_string(), 94853),
+                ("/a/e".to_string(), 584),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let tree = example();
+        let paths = |query: Query| find(&tree, &query);
+        let by_name = Query {
+            name: Some("*.*".to_string()),
+            ..Query::default()
+        };
+        assert_eq!(
+            paths(by_name),
+            vec!["/a/h.lst", "/b.txt", "/c.dat", "/d/d.log", "/d/d.ext"]
+        );
+        let small_files = Query {
+            size: Some("-3000".parse().unwrap()),
+            kind: Some(Kind::File),
+            ..Query::default()
+        };
+        assert_eq!(paths(small_files), vec!["/a/e/i", "/a/g"]);
+        let big_dirs = Query {
+            size: Some(SizeFilter::Above(100000)),
+            kind: Some(Kind::Dir),
+            ..Query::default()
+        };
+        assert_eq!(paths(big_dirs), vec!["/", "/d"]);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("d.*", "d.log"));
+        assert!(glob_match("?.txt", "b.txt"));
+        assert!(glob_match("*a*b*", "xxaybzb"));
+        assert!(!glob_match("*.txt", "b.txt.bak"));
+        assert!(!glob_match("?", ""));
+        assert!(!glob_match("a", "ab"));
+    }
+}
@@ -0,0 +1,291 @@
+/*
+ * Replays a terminal session of `cd` and `ls` commands and rebuilds the file system it shows.
+ * Each line is tokenized on its own, then interpreted against the tree built so far.
+ */
+use crate::fs::{self, Entry, EntryTree, FileSystem};
+use advent_of_code::helpers::tree::{Named, NodeId};
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line<'a> {
+    Cd(&'a str),
+    Ls(Option<&'a str>),
+    Dir(&'a str),
+    File(usize, &'a str),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnknownCommand(String),
+    BadArguments(String),
+    InvalidOutput(String),
+    UnknownDirectory(String),
+    NotADirectory(String),
+    OutputWithoutLs,
+    Conflict(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellError {
+    pub line: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for ShellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ErrorKind::UnknownCommand(cmd) => write!(f, "unknown command \"{}\"", cmd),
+            ErrorKind::BadArguments(usage) => write!(f, "usage: {}", usage),
+            ErrorKind::InvalidOutput(line) => write!(f, "cannot read output \"{}\"", line),
+            ErrorKind::UnknownDirectory(path) => write!(f, "no such directory \"{}\"", path),
+            ErrorKind::NotADirectory(path) => write!(f, "\"{}\" is not a directory", path),
+            ErrorKind::OutputWithoutLs => write!(f, "output without a preceding `ls`"),
+            ErrorKind::Conflict(name) => {
+                write!(f, "\"{}\" was listed differently before", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShellError {}
+
+/// Splits one line of the session into a command or a line of `ls` output.
+/// Names may contain spaces, so everything after the size or `dir` is the name,
+/// and everything after `cd ` or `ls ` is the path.
+pub fn tokenize(line: &str) -> Result<Line<'_>, ErrorKind> {
+    if let Some(command) = line.strip_prefix("$ ") {
+        let (name, path) = command.split_once(' ').unwrap_or((command, ""));
+        return match (name, path) {
+            ("cd", "") => Err(ErrorKind::BadArguments("cd <path>".to_string())),
+            ("cd", path) => Ok(Line::Cd(path)),
+            ("ls", "") => Ok(Line::Ls(None)),
+            ("ls", path) => Ok(Line::Ls(Some(path))),
+            _ => Err(ErrorKind::UnknownCommand(name.to_string())),
+        };
+    }
+
+    let invalid = || ErrorKind::InvalidOutput(line.to_string());
+    let (first, name) = line.split_once(' ').ok_or_else(invalid)?;
+    if name.is_empty() || name.contains('/') {
+        return Err(invalid());
+    }
+    if first == "dir" {
+        Ok(Line::Dir(name))
+    } else {
+        let size = first.parse().map_err(|_| invalid())?;
+        Ok(Line::File(size, name))
+    }
+}
+
+pub struct Shell {
+    tree: EntryTree,
+    cwd: NodeId,
+    /// The directory the last `ls` listed, while its output is being read.
+    listing: Option<NodeId>,
+}
+
+impl Default for Shell {
+    fn default() -> Self {
+        let tree = fs::empty();
+        let cwd = tree.root();
+        Self {
+            tree,
+            cwd,
+            listing: None,
+        }
+    }
+}
+
+impl Shell {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_file_system(self) -> FileSystem {
+        FileSystem::new(self.tree)
+    }
+
+    /// Resolves a path like `/a/b`, `../c` or `a/./b` against the working directory.
+    /// `..` at the root stays at the root, like in a real shell.
+    fn resolve(&self, path: &str) -> Result<NodeId, ErrorKind> {
+        let mut current = if path.starts_with('/') {
+            self.tree.root()
+        } else {
+            self.cwd
+        };
+        for segment in path.split('/') {
+            current = match segment {
+                "" | "." => current,
+                ".." => self.tree.parent(current).unwrap_or(current),
+                name => {
+                    let child = self
+                        .tree
+                        .find_child(current, name)
+                        .ok_or_else(|| ErrorKind::UnknownDirectory(path.to_string()))?;
+                    if !self.tree[child].is_dir() {
+                        return Err(ErrorKind::NotADirectory(path.to_string()));
+                    }
+                    child
+                }
+            };
+        }
+        Ok(current)
+    }
+
+    /// Adds a listed entry unless the same entry was listed before.
+    fn add_entry(&mut self, dir: NodeId, entry: Entry) -> Result<(), ErrorKind> {
+        let name = entry.name();
+        match self.tree.find_child(dir, name) {
+            Some(existing) if self.tree[existing] == entry => Ok(()),
+            Some(_) => Err(ErrorKind::Conflict(name.to_string())),
+            None => {
+                self.tree.add_child(dir, entry);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn execute(&mut self, line: Line) -> Result<(), ErrorKind> {
+        match line {
+            Line::Cd(path) => {
+                self.cwd = self.resolve(path)?;
+                self.listing = None;
+            }
+            Line::Ls(path) => {
+                self.listing = Some(self.resolve(path.unwrap_or("."))?);
+            }
+            Line::Dir(name) => {
+                let dir = self.listing.ok_or(ErrorKind::OutputWithoutLs)?;
+                let name = name.to_string();
+                self.add_entry(dir, Entry::Dir { name })?;
+            }
+            Line::File(size, name) => {
+                let dir = self.listing.ok_or(ErrorKind::OutputWithoutLs)?;
+                let name = name.to_string();
+                self.add_entry(dir, Entry::File { size, name })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replays a whole session, skipping blank lines.
+pub fn parse_tree(input: &str) -> Result<FileSystem, ShellError> {
+    let mut shell = Shell::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        tokenize(line)
+            .and_then(|line| shell.execute(line))
+            .map_err(|kind| ShellError { line: i + 1, kind })?;
+    }
+    Ok(shell.into_file_system())
+}
+
+/// The shortest session that rebuilds `tree`: a `cd` and an `ls` for every directory that has
+/// anything in it, in pre-order.
+pub fn to_log(tree: &FileSystem) -> String {
+    let mut log = String::new();
+    fs::walk(tree, |id, _, path| {
+        let children = tree.children(id);
+        if !tree[id].is_dir() || children.is_empty() {
+            return;
+        }
+        writeln!(log, "$ cd {}\n$ ls", path).unwrap();
+        for &child in children {
+            match &tree[child] {
+                Entry::Dir { name } => writeln!(log, "dir {}", name),
+                Entry::File { size, name } => writeln!(log, "{} {}", size, name),
+            }
+            .unwrap();
+        }
+    });
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(tree: &FileSystem) -> Vec<String> {
+        let mut paths = Vec::new();
+        fs::walk(tree, |_, depth, path| {
+            if depth > 0 {
+                paths.push(path.to_string());
+            }
+        });
+        paths
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("$ cd a/b"), Ok(Line::Cd("a/b")));
+        assert_eq!(tokenize("$ ls"), Ok(Line::Ls(None)));
+        assert_eq!(tokenize("$ ls /a"), Ok(Line::Ls(Some("/a"))));
+        assert_eq!(tokenize("$ cd x y"), Ok(Line::Cd("x y")));
+        assert_eq!(tokenize("$ ls /x y"), Ok(Line::Ls(Some("/x y"))));
+        assert_eq!(tokenize("dir x y"), Ok(Line::Dir("x y")));
+        assert_eq!(tokenize("12 dir x"), Ok(Line::File(12, "dir x")));
+        assert_eq!(
+            tokenize("$ cdrom"),
+            Err(ErrorKind::UnknownCommand("cdrom".to_string()))
+        );
+        assert!(matches!(tokenize("$ cd"), Err(ErrorKind::BadArguments(_))));
+        assert!(matches!(
+            tokenize("x.txt"),
+            Err(ErrorKind::InvalidOutput(_))
+        ));
+    }
+
+    #[test]
+    fn test_paths_and_repeated_ls() {
+        let input = "$ cd /\n$ ls\ndir a\n1 dir x\n$ cd a\n$ ls\ndir b\n$ ls /\ndir a\n1 dir x\n\
+                     $ cd b/../b/.\n$ ls\n5 c\n$ cd /a/b\n$ ls ..\ndir b\n$ ls\n5 c\n$ cd ../../..";
+        let tree = parse_tree(input).unwrap();
+        assert_eq!(paths(&tree), vec!["/a", "/a/b", "/a/b/c", "/dir x"]);
+    }
+
+    #[test]
+    fn test_to_log() {
+        let input = advent_of_code::read_file("examples", 7);
+        let tree = parse_tree(&input).unwrap();
+        let log = to_log(&tree);
+        assert!(log.starts_with("$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n"));
+        let rebuilt = parse_tree(&log).unwrap();
+        assert_eq!(paths(&rebuilt), paths(&tree));
+        assert_eq!(to_log(&rebuilt), log);
+
+        // names with spaces can be entered, so they survive the round trip.
+        let spaced = parse_tree("$ ls\ndir a b\n1 d e\n$ cd a b\n$ ls\n2 f").unwrap();
+        let log = to_log(&spaced);
+        assert_eq!(log, "$ cd /\n$ ls\ndir a b\n1 d e\n$ cd /a b\n$ ls\n2 f\n");
+        assert_eq!(to_log(&parse_tree(&log).unwrap()), log);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |input: &str| parse_tree(input).unwrap_err();
+        assert_eq!(
+            error("$ cd /\n$ ls\ndir a\n$ cd b"),
+            ShellError {
+                line: 4,
+                kind: ErrorKind::UnknownDirectory("b".to_string())
+            }
+        );
+        assert_eq!(
+            error("$ ls\n1 f\n$ cd f").kind,
+            ErrorKind::NotADirectory("f".to_string())
+        );
+        assert_eq!(error("$ cd /\n1 f").kind, ErrorKind::OutputWithoutLs);
+        assert_eq!(
+            error("$ ls\n1 f\n$ ls\n2 f").kind,
+            ErrorKind::Conflict("f".to_string())
+        );
+        assert_eq!(
+            error("$ cd /\n$ ls\ndir a\n$ cd a/b").to_string(),
+            "line 4: no such directory \"a/b\""
+        );
+    }
+}
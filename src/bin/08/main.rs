@@ -0,0 +1,307 @@
+mod render;
+
+use advent_of_code::helpers::grid::{Direction, Grid, Pos};
+use advent_of_code::helpers::sightline::{self, Comparator};
+use std::cmp::Reverse;
+use std::process;
+
+fn parse_input(input: &str) -> Option<Grid<u8>> {
+    Grid::parse(input, |c| c.to_digit(10)?.try_into().ok())
+}
+
+#[cfg(test)]
+fn viewing_distance(grid: &Grid<u8>, pos: Pos, direction: Direction) -> usize {
+    let tree_height = grid[pos];
+    let mut distance = 0;
+    for (_, &other) in grid.ray(pos, direction) {
+        distance += 1;
+        if tree_height <= other {
+            break;
+        }
+    }
+    distance
+}
+
+/// Which ways the trees look and which trees block the view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rules {
+    directions: &'static [Direction],
+    comparator: Comparator,
+}
+
+impl Rules {
+    /// Up, down, left and right, seeing past strictly shorter trees only.
+    const PUZZLE: Rules = Rules {
+        directions: &Direction::CARDINAL,
+        comparator: Comparator::Taller,
+    };
+}
+
+fn visibility(grid: &Grid<u8>, rules: Rules) -> Grid<bool> {
+    sightline::visible_from_any(grid, rules.directions, rules.comparator)
+}
+
+fn scenic_scores(grid: &Grid<u8>, rules: Rules) -> Grid<usize> {
+    sightline::scenic_scores(grid, rules.directions, rules.comparator)
+}
+
+fn count_visible(grid: &Grid<u8>, rules: Rules) -> usize {
+    visibility(grid, rules)
+        .iter()
+        .filter(|(_, &visible)| visible)
+        .count()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid = parse_input(input)?;
+    count_visible(&grid, Rules::PUZZLE).try_into().ok()
+}
+
+/// The tree with the highest scenic score and that score; the first in reading order on ties.
+fn best_view(scores: &Grid<usize>) -> Option<(Pos, usize)> {
+    scores
+        .iter()
+        .max_by_key(|&(pos, &score)| (score, Reverse(pos)))
+        .map(|(pos, &score)| (pos, score))
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid = parse_input(input)?;
+    let (_, max_visibility) = best_view(&scenic_scores(&grid, Rules::PUZZLE))?;
+    max_visibility.try_into().ok()
+}
+
+/// The first `part_one`, looking along all four rays from every tree.
+/// Kept as a reference for the line sweeps.
+#[cfg(test)]
+fn part_one_naive(input: &str) -> Option<u32> {
+    let grid = parse_input(input)?;
+    let visibilities: Grid<bool> = grid.map(|pos, &tree_height| {
+        Direction::CARDINAL
+            .into_iter()
+            .any(|d| grid.ray(pos, d).all(|(_, &other)| tree_height > other))
+    });
+    visibilities
+        .iter()
+        .filter(|(_, &visible)| visible)
+        .count()
+        .try_into()
+        .ok()
+}
+
+/// The first `part_two`, kept as a reference like `part_one_naive`.
+#[cfg(test)]
+fn part_two_naive(input: &str) -> Option<u32> {
+    let grid = parse_input(input)?;
+    let max_visibility = grid
+        .positions()
+        .map(|pos| {
+            Direction::CARDINAL
+                .into_iter()
+                .map(|d| viewing_distance(&grid, pos, d))
+                .product::<usize>()
+        })
+        .max()?;
+    max_visibility.try_into().ok()
+}
+
+struct Args {
+    example: bool,
+    rules: Rules,
+    map: bool,
+    heatmap: bool,
+    best: bool,
+    /// Where to write the scenic scores as a `.pgm` or `.ppm` image.
+    image: Option<String>,
+}
+
+impl Args {
+    /// Answers the two questions when no other output is asked for.
+    fn answer(&self) -> bool {
+        !self.map && !self.heatmap && !self.best && self.image.is_none()
+    }
+}
+
+fn parse_directions(s: &str) -> Result<&'static [Direction], String> {
+    match s {
+        "cardinal" => Ok(&Direction::CARDINAL),
+        "diagonal" => Ok(&Direction::DIAGONAL),
+        "all" => Ok(&Direction::ALL),
+        _ => Err(format!(
+            "unknown directions \"{}\", expected cardinal, diagonal or all",
+            s
+        )),
+    }
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let parsed = Args {
+        example: args.contains(["-e", "--example"]),
+        rules: Rules {
+            directions: args
+                .opt_value_from_fn("--directions", parse_directions)?
+                .unwrap_or(Rules::PUZZLE.directions),
+            comparator: if args.contains("--see-over-equal") {
+                Comparator::TallerOrEqual
+            } else {
+                Comparator::Taller
+            },
+        },
+        map: args.contains("--map"),
+        heatmap: args.contains("--heatmap"),
+        best: args.contains("--best"),
+        image: args.opt_value_from_str("--image")?,
+    };
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments {:?}", remaining),
+        });
+    }
+    Ok(parsed)
+}
+
+fn write_image(path: &str, scores: &Grid<usize>) -> Result<(), String> {
+    let write = if path.ends_with(".pgm") {
+        render::write_pgm
+    } else if path.ends_with(".ppm") {
+        render::write_ppm
+    } else {
+        return Err("the image must be a .pgm or .ppm file".to_string());
+    };
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut file = std::io::BufWriter::new(file);
+    write(scores, &mut file).map_err(|e| e.to_string())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+    let folder = if args.example { "examples" } else { "inputs" };
+    let input = &advent_of_code::read_file(folder, 8);
+
+    if args.answer() && args.rules == Rules::PUZZLE {
+        advent_of_code::solve!(1, part_one, input);
+        advent_of_code::solve!(2, part_two, input);
+        return;
+    }
+
+    let Some(grid) = parse_input(input) else {
+        eprintln!("Failed to read the tree heights");
+        process::exit(1);
+    };
+    let scores = scenic_scores(&grid, args.rules);
+    if args.answer() {
+        println!("Visible trees: {}", count_visible(&grid, args.rules));
+        println!(
+            "Highest scenic score: {}",
+            best_view(&scores).map_or(0, |(_, score)| score)
+        );
+    }
+    if args.map {
+        println!(
+            "{}",
+            render::render_visibility(&visibility(&grid, args.rules))
+        );
+    }
+    if args.heatmap {
+        print!("{}", render::render_heatmap(&grid, &scores));
+    }
+    if args.best {
+        match best_view(&scores) {
+            Some(((i, j), score)) => {
+                println!(
+                    "The best scenic score is {} at row {}, column {}.",
+                    score, i, j
+                )
+            }
+            None => println!("There are no trees."),
+        }
+    }
+    if let Some(path) = &args.image {
+        if let Err(e) = write_image(path, &scores) {
+            eprintln!("Failed to write {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code::helpers::rng::XorShift;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(part_one(&input), Some(21));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(part_two(&input), Some(8));
+    }
+
+    #[test]
+    fn test_best_view() {
+        let grid = parse_input(&advent_of_code::read_file("examples", 8)).unwrap();
+        assert_eq!(
+            best_view(&scenic_scores(&grid, Rules::PUZZLE)),
+            Some(((3, 2), 8))
+        );
+        // every score is 0, so the first tree wins.
+        let flat = Grid::new(2, 2, 0u8);
+        assert_eq!(
+            best_view(&scenic_scores(&flat, Rules::PUZZLE)),
+            Some(((0, 0), 0))
+        );
+    }
+
+    #[test]
+    fn test_other_rules() {
+        let grid = parse_input(&advent_of_code::read_file("examples", 8)).unwrap();
+        let all = Rules {
+            directions: &Direction::ALL,
+            ..Rules::PUZZLE
+        };
+        assert_eq!(count_visible(&grid, all), 22);
+        let lenient = Rules {
+            comparator: Comparator::TallerOrEqual,
+            ..Rules::PUZZLE
+        };
+        // the middle 3 now sees over the 3s to its right.
+        assert_eq!(count_visible(&grid, lenient), 23);
+        assert_eq!(parse_directions("diagonal"), Ok(&Direction::DIAGONAL[..]));
+        assert!(parse_directions("up").is_err());
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let input = advent_of_code::read_file("examples", 8);
+        assert_eq!(part_one(&input), part_one_naive(&input));
+        assert_eq!(part_two(&input), part_two_naive(&input));
+        let mut rng = XorShift::default();
+        for _ in 0..300 {
+            let (height, width) = (1 + rng.below(12), 1 + rng.below(12));
+            // few heights make for many ties, where the sweeps are easiest to get wrong.
+            let heights = 1 + rng.below(10);
+            let input: String = (0..height)
+                .map(|_| {
+                    let row: String = (0..width)
+                        .map(|_| char::from(b'0' + rng.below(heights) as u8))
+                        .collect();
+                    row + "\n"
+                })
+                .collect();
+            assert_eq!(part_one(&input), part_one_naive(&input), "{}", input);
+            assert_eq!(part_two(&input), part_two_naive(&input), "{}", input);
+        }
+    }
+}
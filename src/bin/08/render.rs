@@ -0,0 +1,101 @@
+/*
+ * Pictures of the forest: the visibility map as text, and the scenic scores as a coloured
+ * terminal heatmap or a PGM/PPM image. Scores span several orders of magnitude, so they are
+ * shaded on a log scale.
+ */
+use advent_of_code::helpers::grid::Grid;
+use advent_of_code::ANSI_RESET;
+use std::io::{self, Write};
+
+/// `#` for trees that can be seen from outside the grid, `.` for hidden ones.
+pub fn render_visibility(visible: &Grid<bool>) -> String {
+    visible.map(|_, &v| if v { '#' } else { '.' }).to_string()
+}
+
+/// Scales `score` to `0..=255` against the highest score, logarithmically.
+fn shade(score: usize, max: usize) -> u8 {
+    if max == 0 {
+        return 0;
+    }
+    let t = (score as f64).ln_1p() / (max as f64).ln_1p();
+    (t * 255.0).round() as u8
+}
+
+/// Black through red and yellow to white.
+fn colour(shade: u8) -> [u8; 3] {
+    let t = 3 * shade as usize;
+    let channel = |offset: usize| t.saturating_sub(offset).min(255) as u8;
+    [channel(0), channel(255), channel(510)]
+}
+
+fn shades(scores: &Grid<usize>) -> Grid<u8> {
+    let max = scores.iter().map(|(_, &s)| s).max().unwrap_or(0);
+    scores.map(|_, &s| shade(s, max))
+}
+
+/// Every tree's height on a background coloured by its scenic score, for a truecolor terminal.
+pub fn render_heatmap(heights: &Grid<u8>, scores: &Grid<usize>) -> String {
+    let shades = shades(scores);
+    let mut out = String::new();
+    for i in 0..heights.height() {
+        for j in 0..heights.width() {
+            let [r, g, b] = colour(shades[(i, j)]);
+            // dark text on the bright end of the scale keeps the digits readable.
+            let text = if shades[(i, j)] > 170 { 30 } else { 97 };
+            out += &format!("\x1b[{};48;2;{};{};{}m{}", text, r, g, b, heights[(i, j)]);
+        }
+        out += ANSI_RESET;
+        out += "\n";
+    }
+    out
+}
+
+/// Writes the scenic scores as a binary greyscale PGM image, one pixel per tree.
+pub fn write_pgm(scores: &Grid<usize>, out: &mut impl Write) -> io::Result<()> {
+    write!(out, "P5\n{} {}\n255\n", scores.width(), scores.height())?;
+    let pixels: Vec<u8> = shades(scores).iter().map(|(_, &s)| s).collect();
+    out.write_all(&pixels)
+}
+
+/// Writes the scenic scores as a binary PPM image in the heatmap's colours.
+pub fn write_ppm(scores: &Grid<usize>, out: &mut impl Write) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", scores.width(), scores.height())?;
+    let pixels: Vec<u8> = shades(scores)
+        .iter()
+        .flat_map(|(_, &s)| colour(s))
+        .collect();
+    out.write_all(&pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores() -> Grid<usize> {
+        Grid::from_vec(2, 3, vec![0, 1, 8, 0, 3, 0]).unwrap()
+    }
+
+    #[test]
+    fn test_shade_and_colour() {
+        assert_eq!(shade(0, 8), 0);
+        assert_eq!(shade(8, 8), 255);
+        assert_eq!(shade(1, 8), 80);
+        assert_eq!(shade(0, 0), 0);
+        assert_eq!(colour(0), [0, 0, 0]);
+        assert_eq!(colour(100), [255, 45, 0]);
+        assert_eq!(colour(255), [255, 255, 255]);
+    }
+
+    #[test]
+    fn test_images() {
+        let mut pgm = vec![];
+        write_pgm(&scores(), &mut pgm).unwrap();
+        assert_eq!(pgm, b"P5\n3 2\n255\n\x00\x50\xff\x00\xa1\x00");
+
+        let mut ppm = vec![];
+        write_ppm(&scores(), &mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(ppm.len(), "P6\n3 2\n255\n".len() + 3 * 6);
+        assert_eq!(ppm[ppm.len() - 9..], [0, 0, 0, 255, 228, 0, 0, 0, 0]);
+    }
+}
@@ -2,104 +2,250 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use std::io::Write;
-use std::path::PathBuf;
-use std::{env::temp_dir, io, process::Command};
-use std::{fs, process};
+use advent_of_code::aoc::{self, markdown, AocClient, CachedInput, Unavailable};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 struct Args {
     day: u8,
-    year: Option<i16>,
+    year: Option<i32>,
+    base_url: Option<String>,
+    force: bool,
+    no_puzzle: bool,
+    wait: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
         year: args.opt_value_from_str(["-y", "--year"])?,
+        base_url: args.opt_value_from_str("--base-url")?,
+        force: args.contains(["-f", "--force"]),
+        no_puzzle: args.contains("--no-puzzle"),
+        wait: args.contains(["-w", "--wait"]),
+        day: args.free_from_str()?,
     })
 }
 
-fn remove_file(path: &PathBuf) {
-    #[allow(unused_must_use)]
-    {
-        fs::remove_file(path);
+/// Sleeps until the puzzle unlocks, redrawing a countdown every second.
+fn wait_for_unlock(day: u8, unlocks_at: SystemTime) {
+    loop {
+        let remaining = match unlocks_at.duration_since(SystemTime::now()) {
+            Ok(remaining) if !remaining.is_zero() => remaining,
+            _ => break,
+        };
+        print!(
+            "\rDay {} unlocks in {}... ",
+            day,
+            aoc::format_duration(remaining)
+        );
+        io::stdout().flush().ok();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
     }
+    println!("\rDay {} is unlocked!{}", day, " ".repeat(16));
 }
 
-fn exit_with_status(status: i32, path: &PathBuf) -> ! {
-    remove_file(path);
-    process::exit(status);
+fn download_input(client: &AocClient, year: i32, day: u8, force: bool) -> Result<(), String> {
+    let input_path = format!("src/inputs/{:02}.txt", day);
+    let cache_path = aoc::input_cache_path(year, day);
+
+    match aoc::check_cached_input(&cache_path) {
+        Ok(CachedInput::Valid) if !force => {
+            let input = fs::read_to_string(&cache_path)
+                .map_err(|e| format!("could not read cached input: {}", e))?;
+            if fs::read_to_string(&input_path).is_ok_and(|current| current == input) {
+                println!(
+                    "Input \"{}\" already exists for {}, skipping download. Pass --force to refresh it.",
+                    &input_path, year
+                );
+                return Ok(());
+            }
+            println!(
+                "Using the input cached in \"{}\". Pass --force to download it again.",
+                cache_path.display()
+            );
+            return write_input(&input_path, &input);
+        }
+        Ok(CachedInput::Invalid(reason)) => {
+            println!(
+                "Cached \"{}\" is invalid: {}.",
+                cache_path.display(),
+                reason
+            );
+        }
+        Ok(_) => (),
+        Err(e) => return Err(format!("could not read cached input file: {}", e)),
+    }
+
+    // nothing cached for this year: the input may still be there from before inputs were
+    // cached per year, or put in place by hand.
+    match aoc::check_cached_input(Path::new(&input_path)) {
+        Ok(CachedInput::Valid) if !force => {
+            let input = fs::read_to_string(&input_path)
+                .map_err(|e| format!("could not read existing input file: {}", e))?;
+            match cached_year_of(&input, day) {
+                Some(other) => println!("Existing \"{}\" is the input for {}.", &input_path, other),
+                None => {
+                    println!(
+                        "Input \"{}\" already exists, skipping download. Pass --force to refresh it.",
+                        &input_path
+                    );
+                    return write_cache(&cache_path, &input);
+                }
+            }
+        }
+        Ok(CachedInput::Invalid(reason)) => {
+            println!("Existing \"{}\" is invalid: {}.", &input_path, reason);
+        }
+        Ok(_) => (),
+        Err(e) => return Err(format!("could not read existing input file: {}", e)),
+    }
+
+    println!(
+        "Downloading input for day {}, {} from {}...",
+        day,
+        year,
+        client.base_url()
+    );
+
+    let input = client
+        .input(year, day)
+        .map_err(|e| format!("could not download input: {}", e))?;
+
+    aoc::validate_input(&input).map_err(|e| format!("downloaded input is invalid: {}", e))?;
+
+    write_cache(&cache_path, &input)?;
+    write_input(&input_path, &input)
 }
 
-fn main() {
-    // acquire a temp file path to write aoc-cli output to.
-    // aoc-cli expects this file not to be present - delete just in case.
-    let mut tmp_file_path = temp_dir();
-    tmp_file_path.push("aoc_input_tmp");
-    remove_file(&tmp_file_path);
+/// The year whose cached input for `day` is `input`, if any.
+fn cached_year_of(input: &str, day: u8) -> Option<i32> {
+    fs::read_dir("src/inputs")
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .find(|&year| {
+            fs::read_to_string(aoc::input_cache_path(year, day)).is_ok_and(|cached| cached == input)
+        })
+}
+
+fn write_cache(cache_path: &Path, input: &str) -> Result<(), String> {
+    if let Some(dir) = cache_path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("could not create input cache: {}", e))?;
+    }
+    aoc::write_atomic(cache_path, input)
+        .map_err(|e| format!("could not write cached input file: {}", e))
+}
+
+/// Replaces the input the solutions read, keeping a backup of the previous one.
+fn write_input(input_path: &str, input: &str) -> Result<(), String> {
+    match aoc::backup_file(Path::new(input_path)) {
+        Ok(Some(backup)) => println!("Kept previous input as \"{}\".", backup.display()),
+        Ok(None) => (),
+        Err(e) => return Err(format!("could not back up existing input file: {}", e)),
+    }
+
+    aoc::write_atomic(Path::new(input_path), input)
+        .map_err(|e| format!("could not write input file: {}", e))?;
+    println!("Wrote input to \"{}\".", input_path);
+    Ok(())
+}
 
+fn download_puzzle(client: &AocClient, year: i32, day: u8, force: bool) -> Result<(), String> {
+    let puzzle_path = format!("src/puzzles/{:02}.md", day);
+    let example_path = format!("src/examples/{:02}.txt", day);
+
+    println!("Downloading puzzle for day {}, {}...", day, year);
+
+    let page = client
+        .puzzle(year, day)
+        .map_err(|e| format!("could not download puzzle: {}", e))?;
+
+    let description = markdown::puzzle_to_markdown(&page, client.base_url())
+        .ok_or("could not find a puzzle description on the page.")?;
+    aoc::write_atomic(Path::new(&puzzle_path), &description)
+        .map_err(|e| format!("could not write puzzle file: {}", e))?;
+    println!("Wrote puzzle description to \"{}\".", &puzzle_path);
+
+    let has_example = fs::read_to_string(&example_path).is_ok_and(|e| !e.trim().is_empty());
+    if has_example && !force {
+        println!(
+            "Example \"{}\" already exists, skipping. Pass --force to replace it.",
+            &example_path
+        );
+        return Ok(());
+    }
+    match markdown::first_code_block(&page) {
+        Some(example) => {
+            aoc::write_atomic(Path::new(&example_path), &example)
+                .map_err(|e| format!("could not write example file: {}", e))?;
+            println!("Wrote candidate example to \"{}\".", &example_path);
+        }
+        None => println!("No example found in the puzzle description."),
+    }
+    Ok(())
+}
+
+fn main() {
     let args = match parse_args() {
         Ok(args) => args,
         Err(e) => {
             eprintln!("Failed to process arguments: {}", e);
-            exit_with_status(1, &tmp_file_path);
+            process::exit(1);
         }
     };
 
-    let day_padded = format!("{:02}", args.day);
-    let input_path = format!("src/inputs/{}.txt", day_padded);
-
-    // check if aoc binary exists and is callable.
-    if Command::new("aoc").arg("-V").output().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        exit_with_status(1, &tmp_file_path);
-    }
-
-    let mut cmd_args = vec![];
-
-    if let Some(year) = args.year {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
+    let year = args
+        .year
+        .unwrap_or_else(|| aoc::latest_event_year(SystemTime::now()));
 
-    cmd_args.append(&mut vec![
-        "--input-file".into(),
-        tmp_file_path.to_string_lossy().to_string(),
-        "--day".into(),
-        args.day.to_string(),
-        "download".into(),
-    ]);
-
-    println!("Downloading input with >aoc {}", cmd_args.join(" "));
-
-    match Command::new("aoc").args(cmd_args).output() {
-        Ok(cmd_output) => {
-            io::stdout()
-                .write_all(&cmd_output.stdout)
-                .expect("could not write cmd stdout to pipe.");
-            io::stderr()
-                .write_all(&cmd_output.stderr)
-                .expect("could not write cmd stderr to pipe.");
-            if !cmd_output.status.success() {
-                exit_with_status(1, &tmp_file_path);
-            }
+    match aoc::check_available(year, args.day, SystemTime::now()) {
+        Ok(_) => (),
+        Err(Unavailable::Locked { unlocks_at, .. }) if args.wait => {
+            println!(
+                "Waiting for day {}, {} to unlock at {}.",
+                args.day,
+                year,
+                aoc::format_utc(unlocks_at)
+            );
+            wait_for_unlock(args.day, unlocks_at);
+        }
+        Err(e @ Unavailable::Locked { .. }) => {
+            eprintln!("Day {}, {} is not available yet: {}", args.day, year, e);
+            eprintln!("Pass --wait to wait for it and download it as soon as it unlocks.");
+            process::exit(1);
         }
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
-            exit_with_status(1, &tmp_file_path);
+            eprintln!("{}", e);
+            process::exit(1);
         }
     }
 
-    match fs::copy(&tmp_file_path, &input_path) {
-        Ok(_) => {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            exit_with_status(0, &tmp_file_path);
-        }
+    // only now, so a locked day is reported without a session cookie and one can still be
+    // set up while waiting.
+    let client = match AocClient::from_env(args.base_url) {
+        Ok(client) => client,
         Err(e) => {
-            eprintln!("could not copy downloaded input to input file: {}", e);
-            exit_with_status(1, &tmp_file_path);
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = download_input(&client, year, args.day, args.force) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+
+    if !args.no_puzzle {
+        if let Err(e) = download_puzzle(&client, year, args.day, args.force) {
+            eprintln!("{}", e);
+            process::exit(1);
         }
     }
+
+    println!("---");
+    println!("🎄 Successfully downloaded day {}.", args.day);
 }
@@ -0,0 +1,140 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::aoc::submit::{parse_verdict, GuessLog, Verdict};
+use advent_of_code::aoc::{self, AocClient};
+use std::process::{self, Command};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+struct Args {
+    day: u8,
+    part: u8,
+    year: Option<i32>,
+    base_url: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        base_url: args.opt_value_from_str("--base-url")?,
+        day: args.free_from_str()?,
+        part: args.free_from_str()?,
+    })
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn solve(day: u8, part: u8) -> Result<String, String> {
+    let day_padded = format!("{:02}", day);
+    println!("Running solution for day {}...", day_padded);
+
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &day_padded])
+        .output()
+        .map_err(|e| format!("failed to run solution: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "solution exited with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    advent_of_code::parse_answer(&stdout, part)
+        .ok_or_else(|| format!("part {} is not solved yet.", part))
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(_) => {
+            eprintln!(
+                "Need to specify a day and a part (as integers). example: `cargo submit 7 1`"
+            );
+            process::exit(1);
+        }
+    };
+    if args.part != 1 && args.part != 2 {
+        eprintln!("Part must be 1 or 2.");
+        process::exit(1);
+    }
+    let year = args
+        .year
+        .unwrap_or_else(|| aoc::latest_event_year(SystemTime::now()));
+    if let Err(e) = aoc::check_available(year, args.day, SystemTime::now()) {
+        eprintln!("Cannot submit for day {}, {}: {}", args.day, year, e);
+        process::exit(1);
+    }
+
+    let answer = match solve(args.day, args.part) {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    let log_path = GuessLog::path(year, args.day);
+    let mut log = match GuessLog::load(&log_path) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!(
+                "could not read guesses from \"{}\": {}",
+                log_path.display(),
+                e
+            );
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = log.check(args.part, &answer, unix_now()) {
+        eprintln!("Not submitting \"{}\": {}", answer, refusal);
+        process::exit(1);
+    }
+
+    let client = match AocClient::from_env(args.base_url) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    println!(
+        "Submitting \"{}\" for day {}, {} part {}...",
+        answer, args.day, year, args.part
+    );
+
+    let verdict = match client.submit(year, args.day, args.part, &answer) {
+        Ok(page) => parse_verdict(&page),
+        Err(e) => {
+            eprintln!("could not submit answer: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if log.record(args.part, &answer, &verdict, unix_now()) {
+        if let Err(e) = log.save(&log_path) {
+            eprintln!(
+                "could not save guesses to \"{}\": {}",
+                log_path.display(),
+                e
+            );
+        }
+    }
+
+    println!("---");
+    match verdict {
+        Verdict::Correct => println!("🎄 {}", verdict),
+        _ => {
+            println!("{}", verdict);
+            process::exit(1);
+        }
+    }
+}
@@ -2,3 +2,16 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+
+pub mod bitset;
+pub mod cycle;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod parse;
+pub mod rng;
+pub mod select;
+pub mod sightline;
+pub mod traversal;
+pub mod tree;
@@ -0,0 +1,198 @@
+/*
+ * A set of ASCII bytes packed into a single `u128`, for puzzles over small alphabets.
+ * Everything is `Copy` and allocation free.
+ */
+use std::fmt;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ByteSet {
+    mask: u128,
+}
+
+impl ByteSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn bit(b: u8) -> u128 {
+        assert!(b.is_ascii(), "byte {} is outside the ASCII range", b);
+        1 << b
+    }
+
+    /// Returns whether `b` was newly added. Panics if `b` is not ASCII.
+    pub fn insert(&mut self, b: u8) -> bool {
+        let added = !self.contains(b);
+        self.mask |= Self::bit(b);
+        added
+    }
+
+    /// Returns whether `b` was in the set.
+    pub fn remove(&mut self, b: u8) -> bool {
+        let present = self.contains(b);
+        if present {
+            self.mask &= !Self::bit(b);
+        }
+        present
+    }
+
+    pub fn contains(&self, b: u8) -> bool {
+        b.is_ascii() && self.mask & (1 << b) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self {
+            mask: self.mask & other.mask,
+        }
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            mask: self.mask | other.mask,
+        }
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        Self {
+            mask: self.mask & !other.mask,
+        }
+    }
+
+    /// The members in ascending order.
+    pub fn iter(&self) -> Iter {
+        Iter { mask: self.mask }
+    }
+}
+
+impl FromIterator<u8> for ByteSet {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<u8> for ByteSet {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for b in iter {
+            self.insert(b);
+        }
+    }
+}
+
+impl IntoIterator for ByteSet {
+    type Item = u8;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for ByteSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|b| b as char))
+            .finish()
+    }
+}
+
+pub struct Iter {
+    mask: u128,
+}
+
+impl Iterator for Iter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.mask == 0 {
+            return None;
+        }
+        let b = self.mask.trailing_zeros() as u8;
+        self.mask &= self.mask - 1;
+        Some(b)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+/// The start of the first window of `size` bytes that are all different, if any.
+/// Runs in a single pass, keeping a count per byte value.
+pub fn first_distinct_window(bytes: &[u8], size: usize) -> Option<usize> {
+    if size == 0 {
+        return Some(0);
+    }
+    let mut counts = [0usize; 256];
+    let mut duplicates = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        counts[b as usize] += 1;
+        if counts[b as usize] == 2 {
+            duplicates += 1;
+        }
+        if i >= size {
+            let old = bytes[i - size] as usize;
+            counts[old] -= 1;
+            if counts[old] == 1 {
+                duplicates -= 1;
+            }
+        }
+        if i + 1 >= size && duplicates == 0 {
+            return Some(i + 1 - size);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_operations() {
+        let a: ByteSet = "vJrwpWtwJgWr".bytes().collect();
+        let b: ByteSet = "hcsFMMfFFhFp".bytes().collect();
+        assert_eq!(a.len(), 8);
+        assert!(a.contains(b'J'));
+        assert!(!a.contains(b'x'));
+        assert!(!a.contains(200));
+        assert_eq!(a.intersection(b).iter().collect::<Vec<_>>(), vec![b'p']);
+        assert_eq!(a.union(b).len(), 14);
+        assert_eq!(a.difference(a), ByteSet::new());
+        assert_eq!(
+            "cab"
+                .bytes()
+                .collect::<ByteSet>()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            b"abc"
+        );
+
+        let mut set = ByteSet::new();
+        assert!(set.insert(b'~'));
+        assert!(!set.insert(b'~'));
+        assert!(set.remove(b'~'));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_first_distinct_window() {
+        let input = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(first_distinct_window(input, 4), Some(3));
+        assert_eq!(first_distinct_window(input, 14), Some(5));
+        assert_eq!(first_distinct_window(b"aaaa", 2), None);
+        assert_eq!(first_distinct_window(b"ab", 3), None);
+        assert_eq!(first_distinct_window(b"ab", 2), Some(0));
+    }
+}
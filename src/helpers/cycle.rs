@@ -0,0 +1,219 @@
+/*
+ * Cycle detection for deterministic simulations, where `step` maps every state to the next one.
+ * Once a state repeats the sequence loops forever, so step `n` can be found without running
+ * all `n` steps.
+ */
+use num_traits::PrimInt;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States `start..start + period` repeat forever: step `start + period` equals step `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state equals the state after `n` steps.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Extrapolates a value that grows by the same amount every time round the cycle, such
+    /// as a score or a height. `history[i]` is the value after `i` steps and must cover at least
+    /// the first `start + period` steps.
+    pub fn extrapolate<V: PrimInt>(&self, history: &[V], n: usize) -> V {
+        let end = self.start + self.period;
+        assert!(history.len() > end, "history must cover a full cycle");
+        if n <= end {
+            return history[n];
+        }
+        let laps = (n - self.start) / self.period;
+        let gain = history[end] - history[self.start];
+        let laps = V::from(laps).expect("number of laps does not fit the value type");
+        history[self.equivalent_step(n)] + gain * laps
+    }
+}
+
+/// Floyd's tortoise and hare: constant memory, about three calls to `step` per state.
+pub fn floyd<T: Clone + PartialEq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { start, period }
+}
+
+/// Brent's algorithm: constant memory and usually fewer calls to `step` than `floyd`.
+pub fn brent<T: Clone + PartialEq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+/// Runs `step` until a state repeats, remembering every state on the way.
+/// Returns the cycle and the states after `0..start + period` steps.
+pub fn find_cycle<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let period = states.len() - start;
+            return (Cycle { start, period }, states);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// The state after `n` steps, skipping whole laps once a state repeats.
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, n: usize, mut step: impl FnMut(&S) -> S) -> S {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    while states.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                period: states.len() - start,
+            };
+            return states.swap_remove(cycle.equivalent_step(n));
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: &u32) -> u32 {
+        (x * x + 1) % 1009
+    }
+
+    fn naive(mut x: u32, n: usize) -> u32 {
+        for _ in 0..n {
+            x = step(&x);
+        }
+        x
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        for initial in [0, 2, 3, 500, 1008] {
+            let (cycle, states) = find_cycle(initial, step);
+            assert_eq!(floyd(initial, step), cycle);
+            assert_eq!(brent(initial, step), cycle);
+            assert_eq!(states.len(), cycle.start + cycle.period);
+            assert_eq!(
+                naive(initial, cycle.start),
+                naive(initial, cycle.start + cycle.period)
+            );
+        }
+    }
+
+    #[test]
+    fn test_pure_cycle() {
+        let rotate = |x: &u8| (x + 1) % 5;
+        let expected = Cycle {
+            start: 0,
+            period: 5,
+        };
+        assert_eq!(floyd(0, rotate), expected);
+        assert_eq!(brent(0, rotate), expected);
+        assert_eq!(find_cycle(0, rotate).0, expected);
+    }
+
+    #[test]
+    fn test_state_at() {
+        for n in 0..300 {
+            assert_eq!(state_at(3, n, step), naive(3, n));
+        }
+        let big = state_at(3, 1_000_000_000_000, step);
+        let (cycle, states) = find_cycle(3, step);
+        assert_eq!(big, states[cycle.equivalent_step(1_000_000_000_000)]);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+        let next = |s: &u8| if *s == 4 { 2 } else { s + 1 };
+        let (cycle, states) = find_cycle(0, next);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 2,
+                period: 3,
+            }
+        );
+
+        // the running total of the states passed so far.
+        let mut history = vec![0u64];
+        for &s in states.iter().chain([&states[cycle.start]]) {
+            history.push(history.last().unwrap() + s as u64);
+        }
+
+        let (mut total, mut state) = (0u64, 0u8);
+        for n in 0..100 {
+            assert_eq!(cycle.extrapolate(&history, n), total, "step {}", n);
+            total += state as u64;
+            state = next(&state);
+        }
+    }
+}
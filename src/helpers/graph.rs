@@ -0,0 +1,357 @@
+/*
+ * Graph searches over implicit graphs: the caller passes a closure returning a node's neighbours
+ * (with edge costs for the weighted searches), so no graph structure has to be built up front.
+ * `Grid` gets adapters that walk between edge-adjacent cells.
+ */
+use super::grid::{Grid, Pos};
+use num_traits::{NumCast, Zero};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// What a search found: the cost to reach every visited node and how it got there.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    start: N,
+    order: Vec<N>,
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            start: start.clone(),
+            order: vec![],
+            distances: HashMap::from([(start, zero)]),
+            parents: HashMap::new(),
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// The nodes in the order the search settled them, starting with `start`.
+    pub fn order(&self) -> &[N] {
+        &self.order
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.parents.get(node)
+    }
+
+    /// The nodes from `start` to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search; distances count edges.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let dist = paths.distances[&node];
+        for next in neighbours(&node) {
+            if !paths.contains(&next) {
+                paths.distances.insert(next.clone(), dist + 1);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+        paths.order.push(node);
+    }
+    paths
+}
+
+/// Depth-first search, visiting neighbours in the order they are returned.
+/// Distances are depths in the search tree, which are not shortest distances in general.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut visited = HashSet::new();
+    let mut stack = vec![(start, None)];
+    while let Some((node, parent)) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        if let Some(parent) = parent {
+            let depth = paths.distances[&parent] + 1;
+            paths.distances.insert(node.clone(), depth);
+            paths.parents.insert(node.clone(), parent);
+        }
+        let mut next: Vec<N> = neighbours(&node)
+            .into_iter()
+            .filter(|n| !visited.contains(n))
+            .collect();
+        next.reverse();
+        stack.extend(next.into_iter().map(|n| (n, Some(node.clone()))));
+        paths.order.push(node);
+    }
+    paths
+}
+
+/// A heap entry ordered by its priority alone, lowest first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Reverse(&self.priority).cmp(&Reverse(&other.priority))
+    }
+}
+
+/// Shortest paths from `start` to every reachable node. Edge costs must not be negative.
+pub fn dijkstra<N, C, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::zero());
+    let mut heap = BinaryHeap::from([Queued {
+        priority: C::zero(),
+        cost: C::zero(),
+        node: start,
+    }]);
+    let mut settled = HashSet::new();
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        if !settled.insert(node.clone()) {
+            continue;
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if paths.distance(&next).map_or(true, |d| next_cost < d) {
+                paths.distances.insert(next.clone(), next_cost);
+                paths.parents.insert(next.clone(), node.clone());
+                heap.push(Queued {
+                    priority: next_cost,
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+        paths.order.push(node);
+    }
+    paths
+}
+
+/// The cheapest path from `start` to the first node accepted by `is_goal`.
+/// `heuristic` must never overestimate the remaining cost, or the path may not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::zero());
+    let mut heap = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::zero(),
+        node: start,
+    }]);
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        if paths.distance(&node).is_some_and(|d| cost > d) {
+            continue;
+        }
+        if is_goal(&node) {
+            let path = paths.path_to(&node)?;
+            return Some((cost, path));
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if paths.distance(&next).map_or(true, |d| next_cost < d) {
+                paths.distances.insert(next.clone(), next_cost);
+                paths.parents.insert(next.clone(), node.clone());
+                heap.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+impl<T> Grid<T> {
+    /// BFS over edge-adjacent cells. `can_move(from, to)` decides whether a step is allowed.
+    pub fn bfs(&self, start: Pos, mut can_move: impl FnMut(Pos, Pos) -> bool) -> Paths<Pos, usize> {
+        bfs(start, |&pos| {
+            self.neighbours4(pos)
+                .filter(|&next| can_move(pos, next))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Dijkstra over edge-adjacent cells. `cost(from, to)` returns `None` for blocked steps.
+    pub fn dijkstra<C>(
+        &self,
+        start: Pos,
+        mut cost: impl FnMut(Pos, Pos) -> Option<C>,
+    ) -> Paths<Pos, C>
+    where
+        C: Copy + Ord + Zero,
+    {
+        dijkstra(start, |&pos| {
+            self.neighbours4(pos)
+                .filter_map(|next| Some((next, cost(pos, next)?)))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// A* from `start` to `goal` over edge-adjacent cells, guided by the Manhattan distance.
+    /// Every step must cost at least one for the result to be the cheapest path.
+    pub fn astar<C>(
+        &self,
+        start: Pos,
+        goal: Pos,
+        mut cost: impl FnMut(Pos, Pos) -> Option<C>,
+    ) -> Option<(C, Vec<Pos>)>
+    where
+        C: Copy + Ord + Zero + NumCast,
+    {
+        astar(
+            start,
+            |&pos| {
+                self.neighbours4(pos)
+                    .filter_map(|next| Some((next, cost(pos, next)?)))
+                    .collect::<Vec<_>>()
+            },
+            |&(i, j)| C::from(i.abs_diff(goal.0) + j.abs_diff(goal.1)).unwrap_or_else(C::zero),
+            |&pos| pos == goal,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //  a --1-- b --2-- d
+    //  |       |       |
+    //  4       1       1
+    //  |       |       |
+    //  c --1-- e       f       g (unreachable)
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        let all = [
+            ('a', 'b', 1),
+            ('a', 'c', 4),
+            ('b', 'd', 2),
+            ('b', 'e', 1),
+            ('c', 'e', 1),
+            ('d', 'f', 1),
+        ];
+        all.iter()
+            .filter_map(|&(x, y, c)| match *node {
+                n if n == x => Some((y, c)),
+                n if n == y => Some((x, c)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn unweighted(node: &char) -> Vec<char> {
+        edges(node).into_iter().map(|(n, _)| n).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let paths = bfs('a', unweighted);
+        assert_eq!(paths.order(), &['a', 'b', 'c', 'd', 'e', 'f']);
+        assert_eq!(paths.distance(&'f'), Some(3));
+        assert_eq!(paths.distance(&'e'), Some(2));
+        assert_eq!(paths.path_to(&'f'), Some(vec!['a', 'b', 'd', 'f']));
+        assert_eq!(paths.path_to(&'g'), None);
+        assert_eq!(paths.path_to(&'a'), Some(vec!['a']));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let paths = dfs('a', unweighted);
+        assert_eq!(paths.order(), &['a', 'b', 'd', 'f', 'e', 'c']);
+        assert_eq!(paths.path_to(&'c'), Some(vec!['a', 'b', 'e', 'c']));
+        assert_eq!(paths.distance(&'c'), Some(3));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra('a', edges);
+        assert_eq!(paths.distance(&'c'), Some(3));
+        assert_eq!(paths.path_to(&'c'), Some(vec!['a', 'b', 'e', 'c']));
+        assert_eq!(paths.distance(&'f'), Some(4));
+        assert!(!paths.contains(&'g'));
+    }
+
+    #[test]
+    fn test_astar() {
+        assert_eq!(
+            astar('a', edges, |_| 0, |&n| n == 'c'),
+            Some((3, vec!['a', 'b', 'e', 'c']))
+        );
+        assert_eq!(astar('a', edges, |_| 0, |&n| n == 'g'), None);
+    }
+
+    #[test]
+    fn test_grid_searches() {
+        let grid = Grid::from_chars("S.#.\n..#.\n#...\n..#E").unwrap();
+        let open = |_, to| grid[to] != '#';
+        let paths = grid.bfs((0, 0), open);
+        assert_eq!(paths.distance(&(3, 3)), Some(6));
+        assert_eq!(paths.distance(&(3, 0)), Some(5));
+        assert!(!paths.contains(&(0, 2)));
+
+        let cost = |_, to: Pos| (grid[to] != '#').then_some(1u32);
+        let (total, path) = grid.astar((0, 0), (3, 3), cost).unwrap();
+        assert_eq!(total, 6);
+        assert_eq!(path.len(), 7);
+        assert_eq!(grid.dijkstra((0, 0), cost).distance(&(3, 3)), Some(6));
+        assert_eq!(grid.astar((0, 0), (0, 2), cost), None);
+    }
+}
@@ -0,0 +1,402 @@
+/*
+ * A rectangular grid stored as a single row-major `Vec`.
+ * Positions are `(row, column)` pairs with `(0, 0)` in the top left corner.
+ */
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four directions that share an edge, clockwise from `Up`.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The four directions that share a corner, clockwise from `UpRight`.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    /// All eight directions, clockwise from `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// The `(row, column)` step taken when moving once in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::Right => Direction::Left,
+            Direction::DownRight => Direction::UpLeft,
+            Direction::Down => Direction::Up,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::Left => Direction::Right,
+            Direction::UpLeft => Direction::DownRight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; height * width],
+            height,
+            width,
+        }
+    }
+
+    /// Wraps row-major `data`. Returns `None` if its length does not match the dimensions.
+    pub fn from_vec(height: usize, width: usize, data: Vec<T>) -> Option<Self> {
+        if data.len() != height * width {
+            return None;
+        }
+        Some(Self {
+            data,
+            height,
+            width,
+        })
+    }
+
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .map(&mut f)
+            .collect();
+        Self {
+            data,
+            height,
+            width,
+        }
+    }
+
+    /// Parses a character map, one row per line, converting each character with `f`.
+    /// Returns `None` if `f` rejects a character or the rows differ in length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Option<Self> {
+        let mut data = Vec::new();
+        let mut height = 0;
+        let mut width = None;
+        for line in input.lines() {
+            let before = data.len();
+            for c in line.chars() {
+                data.push(f(c)?);
+            }
+            let len = data.len() - before;
+            if *width.get_or_insert(len) != len {
+                return None;
+            }
+            height += 1;
+        }
+        Some(Self {
+            data,
+            height,
+            width: width.unwrap_or(0),
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn contains(&self, (i, j): Pos) -> bool {
+        i < self.height && j < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.data[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.data[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// The position one step from `pos` in `direction`, if it lies inside the grid.
+    pub fn step(&self, (i, j): Pos, direction: Direction) -> Option<Pos> {
+        let (di, dj) = direction.delta();
+        let next = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+        if self.contains(next) {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| (i, j)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.positions().zip(self.data.iter_mut())
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        assert!(j < self.width, "column {} out of bounds", j);
+        self.data.iter().skip(j).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |j| self.column(j))
+    }
+
+    /// In-bounds positions sharing an edge with `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// In-bounds positions sharing an edge or a corner with `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Walks from `pos` in `direction` until the edge of the grid, excluding `pos` itself.
+    pub fn ray(&self, pos: Pos, direction: Direction) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            pos,
+            direction,
+        }
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            data: self.iter().map(|(pos, v)| f(pos, v)).collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |(i, j)| self[(j, i)].clone())
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        Grid::from_fn(self.width, self.height, |(i, j)| {
+            self[(height - 1 - j, i)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        Grid::from_fn(self.width, self.height, |(i, j)| {
+            self[(j, width - 1 - i)].clone()
+        })
+    }
+}
+
+impl Grid<char> {
+    pub fn from_chars(input: &str) -> Option<Self> {
+        Self::parse(input, Some)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {:?} out of bounds", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {:?} out of bounds", pos))
+    }
+}
+
+/// Renders one line per row with cells written back to back.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Pos,
+    direction: Direction,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Pos, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pos = self.grid.step(self.pos, self.direction)?;
+        Some((self.pos, &self.grid[self.pos]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<u8> {
+        Grid::parse("123\n456", |c| c.to_digit(10).map(|d| d as u8)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+
+        assert!(Grid::from_chars("ab\nc").is_none());
+        assert!(Grid::parse("1x", |c| c.to_digit(10)).is_none());
+        assert!(Grid::from_chars("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = example();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        let columns: Vec<Vec<u8>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        let n4: Vec<Pos> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(n4, vec![(0, 1), (1, 0)]);
+        let n8: Vec<Pos> = grid.neighbours8((1, 1)).collect();
+        assert_eq!(n8, vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = example();
+        let ray: Vec<u8> = grid
+            .ray((1, 0), Direction::Right)
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(ray, vec![5, 6]);
+        let ray: Vec<Pos> = grid
+            .ray((1, 0), Direction::UpRight)
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(ray, vec![(0, 1)]);
+        assert_eq!(grid.ray((0, 0), Direction::Up).count(), 0);
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = example();
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_cw().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_ccw().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
@@ -0,0 +1,229 @@
+/*
+ * Closed integer intervals `[lower, upper]` and sets of them.
+ */
+use num_traits::PrimInt;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    lower: T,
+    upper: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    /// Returns `None` if `lower > upper`.
+    pub fn new(lower: T, upper: T) -> Option<Self> {
+        if lower > upper {
+            None
+        } else {
+            Some(Self { lower, upper })
+        }
+    }
+
+    pub fn lower(&self) -> T {
+        self.lower
+    }
+
+    pub fn upper(&self) -> T {
+        self.upper
+    }
+
+    /// The number of integers in the interval. Overflows if it spans the whole range of `T`.
+    pub fn len(&self) -> T {
+        self.upper - self.lower + T::one()
+    }
+
+    /// A closed interval always holds at least its lower bound.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+
+    /// Whether `other` lies entirely inside `self`.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.lower <= other.lower && other.upper <= self.upper
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.lower <= other.upper && other.lower <= self.upper
+    }
+
+    /// Whether the two intervals overlap or are directly next to each other, like `1-3` and `4-6`.
+    pub fn touches(&self, other: &Self) -> bool {
+        let follows = |a: &Self, b: &Self| a.upper < b.lower && a.upper + T::one() == b.lower;
+        self.overlaps(other) || follows(self, other) || follows(other, self)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.lower.max(other.lower), self.upper.min(other.upper))
+    }
+
+    /// The single interval covering both, if they overlap or touch.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.touches(other) {
+            Self::new(self.lower.min(other.lower), self.upper.max(other.upper))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.lower, self.upper)
+    }
+}
+
+/// A set of integers stored as sorted, disjoint and non-touching intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn coverage(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |acc, interval| acc + interval.len())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.upper < value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    /// Adds `interval`, merging it with every interval it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let start = self
+            .intervals
+            .partition_point(|i| i.upper < interval.lower && !i.touches(&interval));
+        let mut merged = interval;
+        let mut end = start;
+        while let Some(next) = self.intervals.get(end).and_then(|i| merged.union(i)) {
+            merged = next;
+            end += 1;
+        }
+        self.intervals.splice(start..end, [merged]);
+    }
+
+    /// Removes every value of `interval` from the set, splitting intervals where needed.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let mut kept = Vec::with_capacity(self.intervals.len() + 1);
+        for &current in &self.intervals {
+            if !current.overlaps(&interval) {
+                kept.push(current);
+                continue;
+            }
+            if current.lower < interval.lower {
+                kept.push(Interval {
+                    lower: current.lower,
+                    upper: interval.lower - T::one(),
+                });
+            }
+            if interval.upper < current.upper {
+                kept.push(Interval {
+                    lower: interval.upper + T::one(),
+                    upper: current.upper,
+                });
+            }
+        }
+        self.intervals = kept;
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for &interval in &other.intervals {
+            result.insert(interval);
+        }
+        result
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for &interval in &other.intervals {
+            result.remove(interval);
+        }
+        result
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(lower: i32, upper: i32) -> Interval<i32> {
+        Interval::new(lower, upper).unwrap()
+    }
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!(iv(2, 4).len(), 3);
+        assert!(iv(2, 4).contains(4));
+        assert!(!iv(2, 4).contains(5));
+        assert!(iv(2, 8).contains_interval(&iv(3, 7)));
+        assert!(!iv(3, 7).contains_interval(&iv(2, 8)));
+        assert!(iv(5, 7).overlaps(&iv(7, 9)));
+        assert!(!iv(2, 4).overlaps(&iv(6, 8)));
+        assert_eq!(iv(5, 7).intersection(&iv(7, 9)), Some(iv(7, 7)));
+        assert_eq!(iv(2, 4).intersection(&iv(6, 8)), None);
+        assert_eq!(iv(1, 3).union(&iv(4, 6)), Some(iv(1, 6)));
+        assert_eq!(iv(1, 3).union(&iv(5, 6)), None);
+        assert_eq!(iv(-5, -1).to_string(), "-5--1");
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let set: IntervalSet<i32> = [iv(10, 12), iv(1, 3), iv(20, 25), iv(4, 5), iv(11, 21)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.intervals(), &[iv(1, 5), iv(10, 25)]);
+        assert_eq!(set.coverage(), 21);
+        assert!(set.contains(5));
+        assert!(!set.contains(7));
+        assert!(set.contains(25));
+    }
+
+    #[test]
+    fn test_difference() {
+        let a: IntervalSet<i32> = [iv(0, 10), iv(20, 30)].into_iter().collect();
+        let b: IntervalSet<i32> = [iv(3, 4), iv(8, 22), iv(30, 40)].into_iter().collect();
+        let diff = a.difference(&b);
+        assert_eq!(diff.intervals(), &[iv(0, 2), iv(5, 7), iv(23, 29)]);
+        assert_eq!(diff.coverage(), 13);
+        assert_eq!(a.union(&b).intervals(), &[iv(0, 40)]);
+        assert!(a.difference(&a).is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+/*
+ * Number theory helpers, generic over the primitive integer types.
+ * The modular functions compute in `i128` internally, so products of two residues never overflow
+ * for moduli that fit in 64 bits.
+ */
+use num_traits::{PrimInt, Signed};
+
+/// `|a|` widened to `u128`, so that even `T::min_value()` has a magnitude.
+fn magnitude<T: PrimInt>(a: T) -> u128 {
+    match a.to_u128() {
+        Some(a) => a,
+        None => a
+            .to_i128()
+            .expect("every primitive integer fits in an i128 or a u128")
+            .unsigned_abs(),
+    }
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The greatest common divisor, always non-negative. `gcd(0, 0)` is `0`.
+/// Works for `T::min_value()` too, but panics if the result itself does not fit in `T`,
+/// which only happens for `gcd(MIN, 0)` and `gcd(MIN, MIN)`.
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    let g = gcd_u128(magnitude(a), magnitude(b));
+    T::from(g).expect("the gcd does not fit in the type")
+}
+
+/// The least common multiple, always non-negative. Zero if either argument is zero.
+/// Panics if the result does not fit in `T`; see `checked_lcm`.
+pub fn lcm<T: PrimInt>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("the lcm does not fit in the type")
+}
+
+/// Like `lcm`, but returns `None` instead of overflowing.
+pub fn checked_lcm<T: PrimInt>(a: T, b: T) -> Option<T> {
+    let (a, b) = (magnitude(a), magnitude(b));
+    if a == 0 || b == 0 {
+        return Some(T::zero());
+    }
+    T::from((a / gcd_u128(a, b)).checked_mul(b)?)
+}
+
+/// The gcd of all values; `0` for no values.
+pub fn gcd_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), gcd)
+}
+
+/// The lcm of all values; `1` for no values.
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), lcm)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+fn wide<T: PrimInt>(value: T) -> i128 {
+    value
+        .to_i128()
+        .expect("value does not fit in an i128 for modular arithmetic")
+}
+
+fn narrow<T: PrimInt>(value: i128) -> T {
+    T::from(value).expect("a reduced residue always fits the modulus type")
+}
+
+/// `a mod m` in `0..m`, also for negative `a`. `m` must be positive.
+pub fn rem_euclid<T: PrimInt>(a: T, m: T) -> T {
+    assert!(m > T::zero(), "modulus must be positive");
+    narrow(wide(a).rem_euclid(wide(m)))
+}
+
+/// `(a * b) mod m` for residues `a`, `b` in `0..m`.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    // double and add for moduli beyond 64 bits.
+    let (mut a, mut b, mut result) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    result
+}
+
+/// `base^exp mod m` by repeated squaring. `exp` must not be negative and `m` must be positive.
+pub fn mod_pow<T: PrimInt>(base: T, exp: T, m: T) -> T {
+    assert!(exp >= T::zero(), "exponent must not be negative");
+    assert!(m > T::zero(), "modulus must be positive");
+    let m = wide(m);
+    let mut base = wide(base).rem_euclid(m);
+    let mut exp = wide(exp);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    narrow(result)
+}
+
+/// The `x` in `0..m` with `a * x = 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse<T: PrimInt>(a: T, m: T) -> Option<T> {
+    let m = wide(m);
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(wide(a).rem_euclid(m), m);
+    if g == 1 {
+        Some(narrow(x.rem_euclid(m)))
+    } else {
+        None
+    }
+}
+
+/// Solves `x = r (mod m)` for every `(r, m)` pair. Moduli need not be coprime.
+/// Returns the smallest non-negative `x` together with the lcm of the moduli, or `None` if the
+/// congruences contradict each other or the lcm does not fit in `T`.
+pub fn crt<T: PrimInt>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (mut x, mut modulus) = (0i128, 1i128);
+    for (r, m) in congruences {
+        let m = wide(m);
+        if m <= 0 {
+            return None;
+        }
+        let r = wide(r).rem_euclid(m);
+        let (g, p, _) = extended_gcd(modulus, m);
+        if (r - x) % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let t = mul_mod(((r - x) / g).rem_euclid(step), p.rem_euclid(step), step);
+        let next = modulus.checked_mul(step)?;
+        x = (x + modulus.checked_mul(t)?).rem_euclid(next);
+        modulus = next;
+    }
+    Some((T::from(x)?, T::from(modulus)?))
+}
+
+/// The largest `r` with `r * r <= n`. Panics for negative `n`.
+pub fn isqrt<T: PrimInt>(n: T) -> T {
+    assert!(n >= T::zero(), "square root of a negative number");
+    let squared_fits = |r: T| r.checked_mul(&r).is_some_and(|sq| sq <= n);
+    // start from the float estimate and correct its rounding error.
+    let mut r = n.to_f64().and_then(|f| T::from(f.sqrt())).unwrap_or(n);
+    while !squared_fits(r) {
+        r = r - T::one();
+    }
+    while r.checked_add(&T::one()).is_some_and(squared_fits) {
+        r = r + T::one();
+    }
+    r
+}
+
+/// The sum, or `None` on overflow.
+pub fn checked_sum<T: PrimInt>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::zero(), |acc, v| acc.checked_add(&v))
+}
+
+/// The product, or `None` on overflow.
+pub fn checked_product<T: PrimInt>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |acc, v| acc.checked_mul(&v))
+}
+
+/// The sum, clamped to the range of `T` at every step.
+pub fn saturating_sum<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::zero(), |acc, v| acc.saturating_add(v))
+}
+
+fn saturating_mul<T: PrimInt>(a: T, b: T) -> T {
+    a.checked_mul(&b).unwrap_or_else(|| {
+        if (a < T::zero()) != (b < T::zero()) {
+            T::min_value()
+        } else {
+            T::max_value()
+        }
+    })
+}
+
+/// The product, clamped to the range of `T` at every step.
+pub fn saturating_product<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), saturating_mul)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4i32, -6), 12);
+        assert_eq!(lcm(0u8, 3), 0);
+        assert_eq!(gcd_all([24u64, 36, 60]), 12);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(checked_lcm(20u8, 30), Some(60));
+
+        assert_eq!(gcd(i32::MIN, 6), 2);
+        assert_eq!(gcd(i64::MIN, i64::MIN / 2), 1 << 62);
+        assert_eq!(gcd(i128::MIN, 3), 1);
+        assert_eq!(gcd(u128::MAX, 5), 5);
+        assert_eq!(checked_lcm(i8::MIN, 2), None);
+        assert_eq!(checked_lcm(-64i8, 2), Some(64));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240i64, 46), (-7, 3), (0, 5), (17, 0), (12, -18)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_pow(4u32, 13, 497), 445);
+        assert_eq!(mod_pow(2u64, 0, 1), 0);
+        assert_eq!(mod_pow(-2i32, 3, 7), 6);
+        assert_eq!(
+            mod_pow(3u64, 1_000_000_007, u64::MAX - 58),
+            mod_pow(3u128, 1_000_000_007, u64::MAX as u128 - 58) as u64
+        );
+        assert_eq!(mod_inverse(3u32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6u32, 9), None);
+        assert_eq!(rem_euclid(-7i32, 3), 2);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt(Vec::<(u8, u8)>::new()), Some((0, 1)));
+        assert_eq!(crt([(1u8, 16), (2, 17)]), None);
+        assert_eq!(crt([(0u16, 7), (1, 13), (4, 59)]), Some((5019, 5369)));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0u64..2000 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "{}", n);
+        }
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(i128::MAX), 13_043_817_825_332_782_212);
+        assert_eq!(isqrt(255u8), 15);
+    }
+
+    #[test]
+    fn test_checked_and_saturating() {
+        assert_eq!(checked_sum([100u8, 100, 50]), Some(250));
+        assert_eq!(checked_sum([100u8, 100, 100]), None);
+        assert_eq!(checked_product([2i32, 3, 4]), Some(24));
+        assert_eq!(checked_product([i32::MAX, 2]), None);
+        assert_eq!(saturating_sum([100u8, 100, 100]), 255);
+        assert_eq!(saturating_sum([-100i8, -100]), -128);
+        assert_eq!(saturating_product([-100i8, 2]), -128);
+        assert_eq!(saturating_product([-100i8, -2]), 127);
+    }
+}
@@ -0,0 +1,326 @@
+/*
+ * Zero-copy helpers for taking puzzle inputs apart.
+ * Everything borrows from the input and reports failures as a `ParseError` with a 1-based
+ * column, and the line once it is known, instead of panicking.
+ */
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Unknown while a single line is parsed on its own; `parse_lines` and `on_line` fill it in.
+    pub line: Option<usize>,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error produced for a single line to `line` of a larger input.
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}, ", line)?;
+        }
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses every line with `f`, tagging errors with the line they came from.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// Splits the input into groups of lines separated by one or more blank lines.
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks { rest: input }
+}
+
+pub struct Blocks<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        // skip leading blank lines.
+        loop {
+            let line_end = self.rest.find('\n').map_or(self.rest.len(), |i| i + 1);
+            if self.rest.is_empty() || !self.rest[..line_end].trim().is_empty() {
+                break;
+            }
+            self.rest = &self.rest[line_end..];
+        }
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let mut end = 0;
+        while end < self.rest.len() {
+            let line_end = self.rest[end..]
+                .find('\n')
+                .map_or(self.rest.len(), |i| end + i + 1);
+            if self.rest[end..line_end].trim().is_empty() {
+                break;
+            }
+            end = line_end;
+        }
+        let block = self.rest[..end].trim_end_matches(['\n', '\r']);
+        self.rest = &self.rest[end..];
+        Some(block)
+    }
+}
+
+/// Finds every integer in `line`, e.g. `"x=-3, y=4..12"` yields `-3`, `4` and `12`.
+/// A `-` directly after a digit is treated as a separator, so `"2-4"` yields `2` and `4`.
+pub fn ints<T: FromStr>(line: &str) -> Ints<'_, T> {
+    Ints {
+        line,
+        pos: 0,
+        marker: std::marker::PhantomData,
+    }
+}
+
+pub struct Ints<'a, T> {
+    line: &'a str,
+    pos: usize,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<'a, T: FromStr> Iterator for Ints<'a, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.line.as_bytes();
+        let mut start = self.pos;
+        while start < bytes.len() {
+            let negative = bytes[start] == b'-'
+                && bytes.get(start + 1).is_some_and(u8::is_ascii_digit)
+                && (start == 0 || !bytes[start - 1].is_ascii_digit());
+            if negative || bytes[start].is_ascii_digit() {
+                break;
+            }
+            start += 1;
+        }
+        if start >= bytes.len() {
+            self.pos = start;
+            return None;
+        }
+
+        let mut end = start + 1;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        self.pos = end;
+
+        let token = &self.line[start..end];
+        Some(
+            token
+                .parse()
+                .map_err(|_| ParseError::new(start + 1, format!("invalid integer \"{}\"", token))),
+        )
+    }
+}
+
+/// Splits `line` into consecutive columns of `width` characters; the last one may be shorter.
+pub fn columns(line: &str, width: usize) -> Columns<'_> {
+    assert!(width > 0, "column width must be positive");
+    Columns { rest: line, width }
+}
+
+pub struct Columns<'a> {
+    rest: &'a str,
+    width: usize,
+}
+
+impl<'a> Iterator for Columns<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let end = self
+            .rest
+            .char_indices()
+            .nth(self.width)
+            .map_or(self.rest.len(), |(i, _)| i);
+        let (column, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(column)
+    }
+}
+
+/// Matches `input` against a `pattern` where every `{}` captures the text up to the
+/// following literal. Returns the captured fields with their 1-based columns.
+pub fn scan_fields<'a>(pattern: &str, input: &'a str) -> Result<Vec<(usize, &'a str)>, ParseError> {
+    let mut literals = pattern.split("{}");
+    let prefix = literals.next().unwrap_or_default();
+    let mut rest = input
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(1, format!("expected \"{}\"", prefix)))?;
+
+    let mut fields = Vec::new();
+    for literal in literals {
+        let start = input.len() - rest.len();
+        let len = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)
+                .ok_or_else(|| ParseError::new(start + 1, format!("expected \"{}\"", literal)))?
+        };
+        if len == 0 {
+            return Err(ParseError::new(start + 1, "expected a value"));
+        }
+        fields.push((start + 1, &rest[..len]));
+        rest = &rest[len + literal.len()..];
+    }
+
+    if !rest.is_empty() {
+        let column = input.len() - rest.len() + 1;
+        return Err(ParseError::new(column, "unexpected trailing input"));
+    }
+    Ok(fields)
+}
+
+/// Parses one field captured by `scan_fields`. Used by `scan!`.
+pub fn parse_field<T: FromStr>(field: Option<(usize, &str)>) -> Result<T, ParseError> {
+    let (column, text) = field.ok_or_else(|| ParseError::new(1, "pattern has too few fields"))?;
+    text.parse().map_err(|_| {
+        ParseError::new(
+            column,
+            format!(
+                "could not parse \"{}\" as {}",
+                text,
+                std::any::type_name::<T>()
+            ),
+        )
+    })
+}
+
+/// Matches a line against a pattern and parses the `{}` fields into the given types.
+///
+/// ```
+/// use advent_of_code::scan;
+///
+/// let (quantity, from, to) = scan!("move 3 from 1 to 2", "move {} from {} to {}" => u32, usize, usize).unwrap();
+/// assert_eq!((quantity, from, to), (3, 1, 2));
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:expr => $($t:ty),+ $(,)?) => {
+        (|| -> Result<($($t,)+), $crate::helpers::parse::ParseError> {
+            let mut fields = $crate::helpers::parse::scan_fields($pattern, $input)?.into_iter();
+            let values = ($($crate::helpers::parse::parse_field::<$t>(fields.next())?,)+);
+            match fields.next() {
+                Some((column, _)) => Err($crate::helpers::parse::ParseError::new(
+                    column,
+                    "pattern has more fields than types",
+                )),
+                None => Ok(values),
+            }
+        })()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks() {
+        let input = "1000\n2000\n\n4000\n\n\n5000\r\n6000\r\n\r\n7000\n";
+        assert_eq!(
+            blocks(input).collect::<Vec<_>>(),
+            vec!["1000\n2000", "4000", "5000\r\n6000", "7000"]
+        );
+        assert_eq!(blocks("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn test_ints() {
+        let values: Result<Vec<i64>, _> = ints("x=-3, y=4..12 z=2-4").collect();
+        assert_eq!(values, Ok(vec![-3, 4, 12, 2, 4]));
+        let values: Result<Vec<u8>, _> = ints("a 12 b 300").collect();
+        assert_eq!(values, Err(ParseError::new(8, "invalid integer \"300\"")));
+        assert_eq!(ints::<u32>("no numbers").count(), 0);
+    }
+
+    #[test]
+    fn test_columns() {
+        let cols: Vec<&str> = columns("[Z] [M] [P]", 4).collect();
+        assert_eq!(cols, vec!["[Z] ", "[M] ", "[P]"]);
+        assert_eq!(columns("", 4).count(), 0);
+    }
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            crate::scan!("move 13 from 1 to 2", "move {} from {} to {}" => u32, usize, usize),
+            Ok((13, 1, 2))
+        );
+        assert_eq!(
+            crate::scan!("$ cd a b", "$ cd {}" => String),
+            Ok(("a b".to_string(),))
+        );
+        assert_eq!(
+            crate::scan!("move x from 1 to 2", "move {} from {} to {}" => u32, usize, usize),
+            Err(ParseError::new(6, "could not parse \"x\" as u32"))
+        );
+        assert_eq!(
+            crate::scan!("move 1 from 1 to", "move {} from {} to {}" => u32, usize, usize),
+            Err(ParseError::new(13, "expected \" to \""))
+        );
+        assert_eq!(
+            crate::scan!("move 1 from 2 to 3", "move {} from {} to {}" => u32, usize),
+            Err(ParseError::new(18, "pattern has more fields than types"))
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let result = parse_lines("1-2\n3-x", |line| crate::scan!(line, "{}-{}" => u32, u32));
+        assert_eq!(
+            result,
+            Err(ParseError {
+                line: Some(2),
+                column: 3,
+                message: "could not parse \"x\" as u32".to_string()
+            })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, column 3: could not parse \"x\" as u32"
+        );
+        assert_eq!(
+            ParseError::new(4, "expected a value").to_string(),
+            "column 4: expected a value"
+        );
+    }
+}
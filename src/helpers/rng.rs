@@ -0,0 +1,52 @@
+/*
+ * A small seeded random number generator (xorshift64) for generating test cases.
+ * The same seed always gives the same numbers, so a failing case comes back on the next run.
+ */
+
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// Starts from `seed`, which must not be 0: xorshift never leaves 0.
+    pub fn new(seed: u64) -> Self {
+        assert!(seed != 0, "the seed must not be 0");
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "cannot pick a number below 0");
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+impl Default for XorShift {
+    fn default() -> Self {
+        Self::new(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xorshift() {
+        let mut a = XorShift::default();
+        let mut b = XorShift::default();
+        let first: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert!(first.iter().all(|&x| x != 0));
+        assert!((0..1000).all(|_| a.below(7) < 7));
+        assert_ne!(XorShift::new(1).next_u64(), XorShift::new(2).next_u64());
+    }
+}
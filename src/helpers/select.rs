@@ -0,0 +1,146 @@
+/*
+ * Picking the best few items out of an iterator without sorting all of it.
+ * The top-k functions keep a heap of at most `k` items, so they run in O(n log k).
+ */
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// The `k` largest items, largest first.
+pub fn k_largest<T: Ord>(iter: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        return vec![];
+    }
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for item in iter {
+        if heap.len() < k {
+            heap.push(Reverse(item));
+        } else if heap.peek().is_some_and(|Reverse(min)| item > *min) {
+            heap.pop();
+            heap.push(Reverse(item));
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+/// The `k` smallest items, smallest first.
+pub fn k_smallest<T: Ord>(iter: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    k_largest(iter.into_iter().map(Reverse), k)
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+/// Orders items by `rank` alone, so the items themselves need not be `Ord`.
+struct Ranked<R, T> {
+    rank: R,
+    item: T,
+}
+
+impl<R: Ord, T> PartialEq for Ranked<R, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank
+    }
+}
+
+impl<R: Ord, T> Eq for Ranked<R, T> {}
+
+impl<R: Ord, T> PartialOrd for Ranked<R, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: Ord, T> Ord for Ranked<R, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank.cmp(&other.rank)
+    }
+}
+
+/// The `k` items with the largest keys, largest first. Ties keep their original order.
+pub fn max_k_by_key<T, K: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    k: usize,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<T> {
+    let ranked = iter.into_iter().enumerate().map(|(i, item)| Ranked {
+        rank: (key(&item), Reverse(i)),
+        item,
+    });
+    k_largest(ranked, k).into_iter().map(|r| r.item).collect()
+}
+
+/// The `k` items with the smallest keys, smallest first. Ties keep their original order.
+pub fn min_k_by_key<T, K: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    k: usize,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<T> {
+    let ranked = iter.into_iter().enumerate().map(|(i, item)| Ranked {
+        rank: (Reverse(key(&item)), Reverse(i)),
+        item,
+    });
+    k_largest(ranked, k).into_iter().map(|r| r.item).collect()
+}
+
+/// Every item sharing the largest key, in their original order.
+pub fn max_by_key_all<T, K: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<T> {
+    let mut best: Option<K> = None;
+    let mut items = vec![];
+    for item in iter {
+        let k = key(&item);
+        match best.as_ref().map(|b| k.cmp(b)) {
+            Some(Ordering::Less) => continue,
+            Some(Ordering::Equal) => {}
+            None | Some(Ordering::Greater) => {
+                best = Some(k);
+                items.clear();
+            }
+        }
+        items.push(item);
+    }
+    items
+}
+
+/// Every item sharing the smallest key, in their original order.
+pub fn min_by_key_all<T, K: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<T> {
+    max_by_key_all(iter, |item| Reverse(key(item)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_k_largest_and_smallest() {
+        let values = [5, 1, 9, 3, 9, 7, 2];
+        assert_eq!(k_largest(values, 3), vec![9, 9, 7]);
+        assert_eq!(k_smallest(values, 3), vec![1, 2, 3]);
+        assert_eq!(k_largest(values, 0), Vec::<i32>::new());
+        assert_eq!(k_largest([2, 1], 5), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_by_key() {
+        let words = ["pear", "fig", "banana", "kiwi", "apple", "plum"];
+        assert_eq!(max_k_by_key(words, 2, |w| w.len()), vec!["banana", "apple"]);
+        assert_eq!(
+            min_k_by_key(words, 3, |w| w.len()),
+            vec!["fig", "pear", "kiwi"]
+        );
+        assert_eq!(
+            max_by_key_all(words, |w| w.len() == 4),
+            vec!["pear", "kiwi", "plum"]
+        );
+        assert_eq!(min_by_key_all(words, |w| w.len()), vec!["fig"]);
+        assert!(max_by_key_all(Vec::<&str>::new(), |w| w.len()).is_empty());
+    }
+}
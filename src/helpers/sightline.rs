@@ -0,0 +1,194 @@
+/*
+ * Line of sight across a grid of heights, e.g. trees in a forest.
+ * Every query sweeps each line of the grid once, so a direction costs O(cells) no matter
+ * how long the views are.
+ */
+use super::grid::{Direction, Grid, Pos};
+
+/// When one cell can be seen past another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    /// The viewer must be strictly taller; an equal height blocks the view.
+    Taller,
+    /// The viewer must be at least as tall; only taller cells block the view.
+    TallerOrEqual,
+}
+
+impl Comparator {
+    pub fn sees_past<T: Ord>(self, viewer: &T, other: &T) -> bool {
+        match self {
+            Comparator::Taller => viewer > other,
+            Comparator::TallerOrEqual => viewer >= other,
+        }
+    }
+}
+
+/// The lines of the grid that run towards `direction`, each walked backwards from the edge it
+/// points at. By the time a cell is reached, every cell it looks at in `direction` has been seen.
+pub fn lines_towards<T>(
+    grid: &Grid<T>,
+    direction: Direction,
+) -> impl Iterator<Item = impl Iterator<Item = Pos> + '_> + '_ {
+    grid.positions()
+        .filter(move |&pos| grid.step(pos, direction).is_none())
+        .map(move |edge| {
+            std::iter::once(edge).chain(grid.ray(edge, direction.opposite()).map(|(pos, _)| pos))
+        })
+}
+
+/// Whether each cell sees past everything between it and the edge `direction` points at.
+/// Keeps the tallest cell so far per line.
+pub fn visible_from_edge<T: Ord>(
+    grid: &Grid<T>,
+    direction: Direction,
+    comparator: Comparator,
+) -> Grid<bool> {
+    let mut visible = grid.map(|_, _| false);
+    for line in lines_towards(grid, direction) {
+        let mut tallest: Option<&T> = None;
+        for pos in line {
+            let height = &grid[pos];
+            if tallest.map_or(true, |t| comparator.sees_past(height, t)) {
+                visible[pos] = true;
+            }
+            if tallest.map_or(true, |t| height > t) {
+                tallest = Some(height);
+            }
+        }
+    }
+    visible
+}
+
+/// Whether each cell can be seen from the edge in at least one of `directions`.
+pub fn visible_from_any<T: Ord>(
+    grid: &Grid<T>,
+    directions: &[Direction],
+    comparator: Comparator,
+) -> Grid<bool> {
+    let mut visible = grid.map(|_, _| false);
+    for &direction in directions {
+        for (pos, &v) in visible_from_edge(grid, direction, comparator).iter() {
+            visible[pos] |= v;
+        }
+    }
+    visible
+}
+
+/// How many cells each cell sees in `direction`, counting the one that blocks the view.
+/// Along a line the stack holds the cells that could still block the view of a later cell,
+/// as `(index, position)`; anything a newer cell sees past is hidden behind it for good.
+pub fn viewing_distances<T: Ord>(
+    grid: &Grid<T>,
+    direction: Direction,
+    comparator: Comparator,
+) -> Grid<usize> {
+    let mut distances = grid.map(|_, _| 0);
+    for line in lines_towards(grid, direction) {
+        let mut stack: Vec<(usize, Pos)> = vec![];
+        for (k, pos) in line.enumerate() {
+            let height = &grid[pos];
+            while stack
+                .last()
+                .is_some_and(|&(_, other)| comparator.sees_past(height, &grid[other]))
+            {
+                stack.pop();
+            }
+            distances[pos] = stack.last().map_or(k, |&(blocker, _)| k - blocker);
+            stack.push((k, pos));
+        }
+    }
+    distances
+}
+
+/// The product of each cell's viewing distances in all of `directions`.
+pub fn scenic_scores<T: Ord>(
+    grid: &Grid<T>,
+    directions: &[Direction],
+    comparator: Comparator,
+) -> Grid<usize> {
+    let mut scores = grid.map(|_, _| 1);
+    for &direction in directions {
+        for (pos, &d) in viewing_distances(grid, direction, comparator).iter() {
+            scores[pos] *= d;
+        }
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::rng::XorShift;
+
+    fn example() -> Grid<u8> {
+        Grid::parse("30373\n25512\n65332\n33549\n35390", |c| {
+            c.to_digit(10).map(|d| d as u8)
+        })
+        .unwrap()
+    }
+
+    fn count(grid: &Grid<bool>) -> usize {
+        grid.iter().filter(|(_, &v)| v).count()
+    }
+
+    #[test]
+    fn test_puzzle_rules() {
+        let grid = example();
+        let visible = visible_from_any(&grid, &Direction::CARDINAL, Comparator::Taller);
+        assert_eq!(count(&visible), 21);
+        let scores = scenic_scores(&grid, &Direction::CARDINAL, Comparator::Taller);
+        assert_eq!(scores[(3, 2)], 8);
+        assert_eq!(scores[(1, 2)], 4);
+    }
+
+    #[test]
+    fn test_variants() {
+        let grid = example();
+        // the 5 at (1, 2) only sees over the 5 next to it when equal heights do not block.
+        let strict = viewing_distances(&grid, Direction::Left, Comparator::Taller);
+        let lenient = viewing_distances(&grid, Direction::Left, Comparator::TallerOrEqual);
+        assert_eq!((strict[(1, 2)], lenient[(1, 2)]), (1, 2));
+        let up_left = viewing_distances(&grid, Direction::UpLeft, Comparator::Taller);
+        assert_eq!(up_left.row(4), &[0, 1, 1, 3, 1]);
+        assert_eq!(
+            count(&visible_from_any(
+                &grid,
+                &Direction::ALL,
+                Comparator::Taller
+            )),
+            22
+        );
+        assert_eq!(count(&visible_from_any(&grid, &[], Comparator::Taller)), 0);
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let naive_distance = |grid: &Grid<u8>, pos, d, comparator: Comparator| {
+            let mut distance = 0;
+            for (_, other) in grid.ray(pos, d) {
+                distance += 1;
+                if !comparator.sees_past(&grid[pos], other) {
+                    break;
+                }
+            }
+            distance
+        };
+        let mut rng = XorShift::default();
+        for _ in 0..300 {
+            let (height, width) = (1 + rng.below(10), 1 + rng.below(10));
+            let heights = 1 + rng.below(6);
+            let grid = Grid::from_fn(height, width, |_| rng.below(heights) as u8);
+            for comparator in [Comparator::Taller, Comparator::TallerOrEqual] {
+                for d in Direction::ALL {
+                    let visible = grid.map(|pos, h| {
+                        grid.ray(pos, d)
+                            .all(|(_, other)| comparator.sees_past(h, other))
+                    });
+                    let distances = grid.map(|pos, _| naive_distance(&grid, pos, d, comparator));
+                    assert_eq!(visible_from_edge(&grid, d, comparator), visible);
+                    assert_eq!(viewing_distances(&grid, d, comparator), distances);
+                }
+            }
+        }
+    }
+}
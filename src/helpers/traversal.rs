@@ -0,0 +1,278 @@
+/*
+ * Visiting orders for rectangular grids.
+ * `traverse` only produces positions, so it works for any `height` x `width` structure;
+ * `Grid::traverse` and `Grid::traverse_mut` pair those positions with the cells.
+ */
+use super::grid::{Grid, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Left to right, then top to bottom.
+    RowMajor,
+    /// Top to bottom, then left to right.
+    ColumnMajor,
+    /// Clockwise from the top left corner towards the centre.
+    SpiralIn,
+    /// The reverse of `SpiralIn`: from the centre out to the top left corner.
+    SpiralOut,
+    /// Anti-diagonals from the top left corner, each walked from its top end.
+    Diagonal,
+    /// Row by row, alternating left to right and right to left.
+    Snake,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Heading {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+#[derive(Debug, Clone)]
+struct Spiral {
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+    heading: Heading,
+    pos: Pos,
+}
+
+impl Spiral {
+    fn new(height: usize, width: usize) -> Self {
+        Self {
+            top: 0,
+            bottom: height,
+            left: 0,
+            right: width,
+            heading: Heading::Right,
+            pos: (0, 0),
+        }
+    }
+
+    /// Moves the cursor one step, turning clockwise and shrinking the bounds at each corner.
+    /// After the last cell the cursor may leave the grid, which is fine as nothing reads it.
+    fn advance(&mut self) {
+        let (i, j) = &mut self.pos;
+        match self.heading {
+            Heading::Right if *j + 1 < self.right => *j += 1,
+            Heading::Right => {
+                self.top += 1;
+                self.heading = Heading::Down;
+                *i += 1;
+            }
+            Heading::Down if *i + 1 < self.bottom => *i += 1,
+            Heading::Down => {
+                self.right -= 1;
+                self.heading = Heading::Left;
+                *j = j.wrapping_sub(1);
+            }
+            Heading::Left if *j > self.left => *j -= 1,
+            Heading::Left => {
+                self.bottom -= 1;
+                self.heading = Heading::Up;
+                *i = i.wrapping_sub(1);
+            }
+            Heading::Up if *i > self.top => *i -= 1,
+            Heading::Up => {
+                self.left += 1;
+                self.heading = Heading::Right;
+                *j += 1;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Traversal {
+    order: Order,
+    height: usize,
+    width: usize,
+    index: usize,
+    spiral: Spiral,
+    buffer: Vec<Pos>,
+}
+
+/// Iterates over every position of a `height` x `width` grid exactly once in the given order.
+pub fn traverse(order: Order, height: usize, width: usize) -> Traversal {
+    let mut traversal = Traversal {
+        order,
+        height,
+        width,
+        index: 0,
+        spiral: Spiral::new(height, width),
+        buffer: Vec::new(),
+    };
+    if order == Order::SpiralOut {
+        traversal.buffer = traverse(Order::SpiralIn, height, width).collect();
+        traversal.buffer.reverse();
+    }
+    traversal
+}
+
+impl Traversal {
+    fn total(&self) -> usize {
+        self.height * self.width
+    }
+
+    /// The position of the `k`-th cell along the anti-diagonals.
+    fn diagonal(&self, mut k: usize) -> Pos {
+        let mut d: usize = 0;
+        loop {
+            let first = d.saturating_sub(self.width - 1);
+            let last = d.min(self.height - 1);
+            let len = last + 1 - first;
+            if k < len {
+                return (first + k, d - first - k);
+            }
+            k -= len;
+            d += 1;
+        }
+    }
+}
+
+impl Iterator for Traversal {
+    type Item = Pos;
+
+    fn next(&mut self) -> Option<Pos> {
+        if self.index >= self.total() {
+            return None;
+        }
+        let k = self.index;
+        let (h, w) = (self.height, self.width);
+        let pos = match self.order {
+            Order::RowMajor => (k / w, k % w),
+            Order::ColumnMajor => (k % h, k / h),
+            Order::Snake if (k / w) % 2 == 0 => (k / w, k % w),
+            Order::Snake => (k / w, w - 1 - k % w),
+            Order::Diagonal => self.diagonal(k),
+            Order::SpiralIn => {
+                let pos = self.spiral.pos;
+                self.spiral.advance();
+                pos
+            }
+            Order::SpiralOut => self.buffer[k],
+        };
+        self.index += 1;
+        Some(pos)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Traversal {}
+
+impl<T> Grid<T> {
+    pub fn traverse(&self, order: Order) -> impl Iterator<Item = (Pos, &T)> {
+        traverse(order, self.height(), self.width()).map(move |pos| (pos, &self[pos]))
+    }
+
+    pub fn traverse_mut(&mut self, order: Order) -> TraverseMut<'_, T> {
+        let positions = traverse(order, self.height(), self.width());
+        let width = self.width();
+        let cells = self.iter_mut().map(|(_, cell)| Some(cell)).collect();
+        TraverseMut {
+            positions,
+            width,
+            cells,
+        }
+    }
+}
+
+/// Hands out each cell's `&mut` exactly once, in traversal order.
+pub struct TraverseMut<'a, T> {
+    positions: Traversal,
+    width: usize,
+    cells: Vec<Option<&'a mut T>>,
+}
+
+impl<'a, T> Iterator for TraverseMut<'a, T> {
+    type Item = (Pos, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next()?;
+        let cell = self.cells[pos.0 * self.width + pos.1].take()?;
+        Some((pos, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDERS: [Order; 6] = [
+        Order::RowMajor,
+        Order::ColumnMajor,
+        Order::SpiralIn,
+        Order::SpiralOut,
+        Order::Diagonal,
+        Order::Snake,
+    ];
+
+    fn numbered(order: Order, height: usize, width: usize) -> String {
+        let mut grid = Grid::new(height, width, 0);
+        for (n, (_, cell)) in grid.traverse_mut(order).enumerate() {
+            *cell = n;
+        }
+        grid.map(|_, n| format!("{:>2}", n)).to_string()
+    }
+
+    #[test]
+    fn test_orders() {
+        assert_eq!(numbered(Order::RowMajor, 2, 3), " 0 1 2\n 3 4 5");
+        assert_eq!(numbered(Order::ColumnMajor, 2, 3), " 0 2 4\n 1 3 5");
+        assert_eq!(numbered(Order::Snake, 3, 2), " 0 1\n 3 2\n 4 5");
+        assert_eq!(numbered(Order::Diagonal, 3, 3), " 0 1 3\n 2 4 6\n 5 7 8");
+        assert_eq!(
+            numbered(Order::SpiralIn, 3, 4),
+            " 0 1 2 3\n 91011 4\n 8 7 6 5"
+        );
+        assert_eq!(numbered(Order::SpiralOut, 2, 2), " 3 2\n 0 1");
+    }
+
+    #[test]
+    fn test_every_position_once() {
+        for order in ORDERS {
+            for height in 1..6 {
+                for width in 1..6 {
+                    let mut seen = Grid::new(height, width, 0);
+                    let traversal = traverse(order, height, width);
+                    assert_eq!(traversal.len(), height * width);
+                    for pos in traversal {
+                        seen[pos] += 1;
+                    }
+                    assert!(
+                        seen.iter().all(|(_, &n)| n == 1),
+                        "{:?} {}x{}",
+                        order,
+                        height,
+                        width
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spiral_steps_to_neighbours() {
+        for height in 1..7 {
+            for width in 1..7 {
+                let path: Vec<Pos> = traverse(Order::SpiralIn, height, width).collect();
+                for step in path.windows(2) {
+                    let (a, b) = (step[0], step[1]);
+                    assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(traverse(Order::SpiralIn, 0, 4).count(), 0);
+        assert_eq!(traverse(Order::Diagonal, 3, 0).count(), 0);
+    }
+}
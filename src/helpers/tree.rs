@@ -0,0 +1,289 @@
+/*
+ * An arena-allocated tree: nodes live in one `Vec` and refer to each other by index.
+ * Traversals and folds are iterative, so arbitrarily deep trees do not overflow the stack.
+ */
+use std::ops::{Index, IndexMut};
+
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node<T> {
+    val: T,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree<T> {
+    nodes: Vec<Node<T>>,
+}
+
+/// Values that can be looked up by name, e.g. files and directories.
+pub trait Named {
+    fn name(&self) -> &str;
+}
+
+impl<T> Tree<T> {
+    pub const ROOT: NodeId = 0;
+
+    pub fn new(root: T) -> Self {
+        Self {
+            nodes: vec![Node {
+                val: root,
+                parent: None,
+                children: vec![],
+            }],
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        Self::ROOT
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// A tree always has a root, so it is never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn add_child(&mut self, parent: NodeId, val: T) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            val,
+            parent: Some(parent),
+            children: vec![],
+        });
+        self.nodes[parent].children.push(id);
+        id
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.nodes.get(id).map(|n| &n.val)
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.nodes.get_mut(id).map(|n| &mut n.val)
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id].parent
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id].children
+    }
+
+    /// The parent, grandparent, ... of `id`, up to and including the root.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), move |&p| self.parent(p))
+    }
+
+    pub fn depth(&self, id: NodeId) -> usize {
+        self.ancestors(id).count()
+    }
+
+    /// All node ids in insertion order.
+    pub fn ids(&self) -> std::ops::Range<NodeId> {
+        0..self.nodes.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.nodes.iter().enumerate().map(|(id, n)| (id, &n.val))
+    }
+
+    /// Visits `from` and its descendants, each node before its children.
+    pub fn pre_order(&self, from: NodeId) -> PreOrder<'_, T> {
+        PreOrder {
+            tree: self,
+            stack: vec![from],
+        }
+    }
+
+    /// Visits `from` and its descendants, each node after its children.
+    pub fn post_order(&self, from: NodeId) -> PostOrder<'_, T> {
+        PostOrder {
+            tree: self,
+            stack: vec![(from, false)],
+        }
+    }
+
+    /// Computes a value for every node from its own value and its children's results,
+    /// visiting each node once, bottom-up. The result is indexed by `NodeId`.
+    pub fn fold_up<R>(&self, mut f: impl FnMut(&T, &mut dyn Iterator<Item = &R>) -> R) -> Vec<R> {
+        let mut results: Vec<Option<R>> = self.nodes.iter().map(|_| None).collect();
+        for id in self.post_order(Self::ROOT) {
+            let node = &self.nodes[id];
+            let mut children = node.children.iter().filter_map(|&c| results[c].as_ref());
+            let result = f(&node.val, &mut children);
+            results[id] = Some(result);
+        }
+        results
+            .into_iter()
+            .map(|r| r.expect("every node is reachable from the root"))
+            .collect()
+    }
+}
+
+impl<T: Named> Tree<T> {
+    pub fn find_child(&self, id: NodeId, name: &str) -> Option<NodeId> {
+        self.children(id)
+            .iter()
+            .copied()
+            .find(|&c| self.nodes[c].val.name() == name)
+    }
+
+    /// Resolves a `/`-separated path relative to `from`. A leading `/` starts at the root,
+    /// `.` stays in place and `..` moves to the parent (staying put at the root).
+    pub fn lookup(&self, from: NodeId, path: &str) -> Option<NodeId> {
+        let mut current = if path.starts_with('/') {
+            Self::ROOT
+        } else {
+            from
+        };
+        for segment in path.split('/') {
+            current = match segment {
+                "" | "." => current,
+                ".." => self.parent(current).unwrap_or(current),
+                name => self.find_child(current, name)?,
+            };
+        }
+        Some(current)
+    }
+}
+
+impl<T> Index<NodeId> for Tree<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &T {
+        &self.nodes[id].val
+    }
+}
+
+impl<T> IndexMut<NodeId> for Tree<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.nodes[id].val
+    }
+}
+
+pub struct PreOrder<'a, T> {
+    tree: &'a Tree<T>,
+    stack: Vec<NodeId>,
+}
+
+impl<'a, T> Iterator for PreOrder<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.stack.pop()?;
+        self.stack
+            .extend(self.tree.children(id).iter().rev().copied());
+        Some(id)
+    }
+}
+
+pub struct PostOrder<'a, T> {
+    tree: &'a Tree<T>,
+    stack: Vec<(NodeId, bool)>,
+}
+
+impl<'a, T> Iterator for PostOrder<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        loop {
+            let (id, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(id);
+            }
+            self.stack.push((id, true));
+            self.stack
+                .extend(self.tree.children(id).iter().rev().map(|&c| (c, false)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Named for &str {
+        fn name(&self) -> &str {
+            self
+        }
+    }
+
+    //   /
+    //   ├── a
+    //   │   ├── c
+    //   │   └── d
+    //   └── b
+    fn example() -> Tree<&'static str> {
+        let mut tree = Tree::new("/");
+        let a = tree.add_child(Tree::<&str>::ROOT, "a");
+        tree.add_child(Tree::<&str>::ROOT, "b");
+        tree.add_child(a, "c");
+        tree.add_child(a, "d");
+        tree
+    }
+
+    #[test]
+    fn test_navigation() {
+        let tree = example();
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.children(0), &[1, 2]);
+        assert_eq!(tree.parent(3), Some(1));
+        assert_eq!(tree.parent(0), None);
+        assert_eq!(tree.ancestors(4).collect::<Vec<_>>(), vec![1, 0]);
+        assert_eq!(tree.depth(4), 2);
+        assert_eq!(tree[4], "d");
+    }
+
+    #[test]
+    fn test_lookup() {
+        let tree = example();
+        assert_eq!(tree.find_child(0, "b"), Some(2));
+        assert_eq!(tree.find_child(0, "c"), None);
+        assert_eq!(tree.lookup(0, "a/d"), Some(4));
+        assert_eq!(tree.lookup(3, "../d"), Some(4));
+        assert_eq!(tree.lookup(3, "/b"), Some(2));
+        assert_eq!(tree.lookup(0, "../.."), Some(0));
+        assert_eq!(tree.lookup(0, "a/x"), None);
+    }
+
+    #[test]
+    fn test_orders() {
+        let tree = example();
+        let names = |ids: Vec<NodeId>| ids.into_iter().map(|id| tree[id]).collect::<Vec<_>>();
+        assert_eq!(
+            names(tree.pre_order(0).collect()),
+            vec!["/", "a", "c", "d", "b"]
+        );
+        assert_eq!(
+            names(tree.post_order(0).collect()),
+            vec!["c", "d", "a", "b", "/"]
+        );
+        assert_eq!(names(tree.post_order(1).collect()), vec!["c", "d", "a"]);
+    }
+
+    #[test]
+    fn test_fold_up() {
+        let tree = example();
+        let leaves = tree.fold_up(|_, children| children.sum::<usize>().max(1));
+        assert_eq!(leaves, vec![3, 2, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        let mut tree = Tree::new("root");
+        let mut id = tree.root();
+        for _ in 0..100_000 {
+            id = tree.add_child(id, "x");
+        }
+        let depths = tree.fold_up(|_, children| children.next().map_or(0, |d| d + 1));
+        assert_eq!(depths[0], 100_000);
+        assert_eq!(tree.depth(id), 100_000);
+    }
+}
@@ -6,6 +6,7 @@
 use std::env;
 use std::fs;
 
+pub mod aoc;
 pub mod helpers;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -77,6 +78,20 @@ pub fn parse_exec_time(output: &str) -> f64 {
     })
 }
 
+/// Extracts the answer printed by `solve!` for `part` from a solution's output.
+pub fn parse_answer(output: &str, part: u8) -> Option<String> {
+    let header = format!("Part {}{}", part, ANSI_RESET);
+    let mut lines = output.lines().skip_while(|l| !l.contains(&header)).skip(1);
+    let line = lines.find(|l| !l.trim().is_empty())?;
+    let answer = &line[..line.find("(elapsed:")?];
+    let answer = answer.trim_end().trim_end_matches(ANSI_ITALIC).trim_end();
+    if answer.is_empty() {
+        None
+    } else {
+        Some(answer.to_string())
+    }
+}
+
 /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
 #[cfg(test)]
 macro_rules! assert_approx_eq {
@@ -122,4 +137,25 @@ mod tests {
             10400.50_f64
         );
     }
+
+    #[test]
+    fn test_parse_answer() {
+        let output = format!(
+            "🎄 {b}Part 1{r} 🎄\n24000 {i}(elapsed: 12.20µs){r}\n🎄 {b}Part 2{r} 🎄\nnot solved.\n",
+            b = ANSI_BOLD,
+            i = ANSI_ITALIC,
+            r = ANSI_RESET
+        );
+        assert_eq!(parse_answer(&output, 1), Some("24000".to_string()));
+        assert_eq!(parse_answer(&output, 2), None);
+        assert_eq!(parse_answer(&output, 3), None);
+
+        let output = format!(
+            "🎄 {b}Part 2{r} 🎄\nMCD {i}(elapsed: 1.00ms){r}\n",
+            b = ANSI_BOLD,
+            i = ANSI_ITALIC,
+            r = ANSI_RESET
+        );
+        assert_eq!(parse_answer(&output, 2), Some("MCD".to_string()));
+    }
 }